@@ -0,0 +1,134 @@
+use crate::{write_grid, Maze, MazeCell, MazeError, RenderStyle, Wall};
+use std::fmt;
+
+/// A maze whose size is known at compile time, with its walls stored inline rather
+/// than on the heap
+/// Walls are laid out like those of `Maze`, with the north walls of each row followed
+/// by the east walls of each column. The north walls of the top row and the east walls
+/// of the right-most column are the maze edge and are always closed.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ConstMaze<const W: usize, const H: usize> {
+    north_walls: [[Wall; W]; H],
+    east_walls: [[Wall; H]; W],
+}
+
+impl<const W: usize, const H: usize> ConstMaze<W, H> {
+    /// Create a new maze of all closed walls
+    pub fn new() -> Self {
+        ConstMaze {
+            north_walls: [[Wall::Closed; W]; H],
+            east_walls: [[Wall::Closed; H]; W],
+        }
+    }
+
+    /// Opens the wall shared by two orthogonally adjacent cells
+    pub fn connect(&mut self, a: MazeCell, b: MazeCell) -> Result<(), MazeError> {
+        for &cell in &[a, b] {
+            if cell.x as usize >= W || cell.y as usize >= H {
+                return Err(MazeError::CellOutOfBounds(cell));
+            }
+        }
+
+        let (x, y) = (a.x.min(b.x) as usize, a.y.min(b.y) as usize);
+        if a.x == b.x && (a.y + 1 == b.y || b.y + 1 == a.y) {
+            self.north_walls[y][x] = Wall::Open;
+        } else if a.y == b.y && (a.x + 1 == b.x || b.x + 1 == a.x) {
+            self.east_walls[x][y] = Wall::Open;
+        } else {
+            return Err(MazeError::NotAdjacent(a, b));
+        }
+
+        Ok(())
+    }
+
+    /// Copies this maze into a heap allocated `Maze`
+    pub fn to_maze(&self) -> Maze {
+        let mut maze = Maze::new(W as u32, H as u32);
+        for x in 0..W {
+            for y in 0..H {
+                if let Some(index) = maze.north_wall_index_for_cell(x as u32, y as u32) {
+                    maze.walls[index] = self.north_walls[y][x];
+                }
+                if let Some(index) = maze.east_wall_index_for_cell(x as u32, y as u32) {
+                    maze.walls[index] = self.east_walls[x][y];
+                }
+            }
+        }
+
+        maze
+    }
+}
+
+impl<const W: usize, const H: usize> Default for ConstMaze<W, H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const W: usize, const H: usize> fmt::Display for ConstMaze<W, H> {
+    /// Renders the maze like `Maze` does, straight from the inline walls
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write_grid(
+            f,
+            (W as u32, H as u32),
+            |x, y| {
+                let (x, y) = (x as usize, y as usize);
+                x == 0 || x == W || self.east_walls[x - 1][y] == Wall::Closed
+            },
+            |x, y| {
+                let (x, y) = (x as usize, y as usize);
+                y == 0 || y == H || self.north_walls[y - 1][x] == Wall::Closed
+            },
+            RenderStyle::Sharp,
+            |_cell| "   ",
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_renders_like_maze() {
+        let maze = ConstMaze::<3, 3>::new();
+
+        assert_eq!(Maze::new(3, 3).to_string(), maze.to_string());
+    }
+
+    #[test]
+    fn connect_matches_maze() {
+        let mut const_maze = ConstMaze::<3, 2>::new();
+        let mut maze = Maze::new(3, 2);
+        let edges = [
+            (MazeCell::new(0, 0), MazeCell::new(1, 0)),
+            (MazeCell::new(2, 1), MazeCell::new(2, 0)),
+            (MazeCell::new(1, 1), MazeCell::new(0, 1)),
+        ];
+
+        for &(a, b) in &edges {
+            const_maze.connect(a, b).unwrap();
+        }
+        maze.carve_edges(&edges).unwrap();
+
+        assert_eq!(maze, const_maze.to_maze());
+        assert_eq!(maze.to_string(), const_maze.to_string());
+    }
+
+    #[test]
+    fn connect_errors() {
+        let mut maze = ConstMaze::<3, 2>::new();
+
+        assert_eq!(
+            Err(MazeError::NotAdjacent(
+                MazeCell::new(0, 0),
+                MazeCell::new(1, 1)
+            )),
+            maze.connect(MazeCell::new(0, 0), MazeCell::new(1, 1))
+        );
+        assert_eq!(
+            Err(MazeError::CellOutOfBounds(MazeCell::new(0, 2))),
+            maze.connect(MazeCell::new(0, 1), MazeCell::new(0, 2))
+        );
+    }
+}
@@ -0,0 +1,273 @@
+use crate::{MazeCell, MazeRng, RandRng, Wall, LINE_ENDING};
+use rand::rngs::StdRng;
+use rand::SeedableRng as _;
+
+/// The six sides of a pointy topped hexagonal cell
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum HexDirection {
+    East,
+    NorthEast,
+    NorthWest,
+    West,
+    SouthWest,
+    SouthEast,
+}
+
+impl HexDirection {
+    pub const ALL: [HexDirection; 6] = [
+        HexDirection::East,
+        HexDirection::NorthEast,
+        HexDirection::NorthWest,
+        HexDirection::West,
+        HexDirection::SouthWest,
+        HexDirection::SouthEast,
+    ];
+}
+
+/// A maze of hexagonal cells, where every cell has up to six neighbors
+/// Rows are counted up from the bottom as in `Maze`, and every odd row is shifted half
+/// a cell to the east, so cell (0, 1) sits between cells (0, 0) and (1, 0).
+/// Each cell stores its east, north east, and north west walls; its other walls are
+/// stored by the neighbor on that side. Walls along the maze edge are always closed.
+#[derive(Debug, PartialEq, Clone)]
+pub struct HexMaze {
+    width: u32,
+    height: u32,
+    walls: Vec<Wall>,
+}
+
+impl HexMaze {
+    /// Create a new maze of all closed walls
+    /// Panics if height or width are < 1
+    fn new(width: u32, height: u32) -> Self {
+        assert!(width > 0 && height > 0);
+
+        HexMaze {
+            width,
+            height,
+            walls: vec![Wall::Closed; (width * height * 3) as usize],
+        }
+    }
+
+    pub fn recursive_backtracker(width: u32, height: u32) -> Self {
+        Self::recursive_backtracker_with_rng(width, height, &mut RandRng(rand::thread_rng()))
+    }
+
+    pub fn recursive_backtracker_seeded(width: u32, height: u32, seed: u64) -> Self {
+        Self::recursive_backtracker_with_rng(
+            width,
+            height,
+            &mut RandRng(StdRng::seed_from_u64(seed)),
+        )
+    }
+
+    pub fn recursive_backtracker_with_rng(width: u32, height: u32, rng: &mut impl MazeRng) -> Self {
+        let mut maze = Self::new(width, height);
+        let mut visited = vec![false; (width * height) as usize];
+
+        let start = MazeCell::new(
+            rng.next_index(width as usize) as u32,
+            rng.next_index(height as usize) as u32,
+        );
+        visited[maze.cell_index(start)] = true;
+        let mut stack = vec![start];
+
+        while let Some(&current) = stack.last() {
+            let unvisited: Vec<(HexDirection, MazeCell)> = HexDirection::ALL
+                .iter()
+                .filter_map(|&direction| {
+                    maze.neighbor(current, direction)
+                        .map(|neighbor| (direction, neighbor))
+                })
+                .filter(|&(_direction, neighbor)| !visited[maze.cell_index(neighbor)])
+                .collect();
+
+            if unvisited.is_empty() {
+                // dead end, backtrack until a cell with unvisited neighbors is found
+                stack.pop();
+                continue;
+            }
+
+            let (direction, next) = unvisited[rng.next_index(unvisited.len())];
+            let index = maze.wall_index(current, direction).unwrap();
+            maze.walls[index] = Wall::Open;
+            visited[maze.cell_index(next)] = true;
+            stack.push(next);
+        }
+
+        maze
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns the cell on the given side of cell, or None at the maze edge
+    pub fn neighbor(&self, cell: MazeCell, direction: HexDirection) -> Option<MazeCell> {
+        let (x, y) = (i64::from(cell.x), i64::from(cell.y));
+        // odd rows are shifted east, so their diagonal neighbors are one column further east
+        let shift = y % 2;
+        let (x, y) = match direction {
+            HexDirection::East => (x + 1, y),
+            HexDirection::NorthEast => (x + shift, y + 1),
+            HexDirection::NorthWest => (x + shift - 1, y + 1),
+            HexDirection::West => (x - 1, y),
+            HexDirection::SouthWest => (x + shift - 1, y - 1),
+            HexDirection::SouthEast => (x + shift, y - 1),
+        };
+
+        if x < 0 || y < 0 || x >= i64::from(self.width) || y >= i64::from(self.height) {
+            None
+        } else {
+            Some(MazeCell::new(x as u32, y as u32))
+        }
+    }
+
+    /// Returns true if the wall on the given side of cell is open
+    pub fn is_open(&self, cell: MazeCell, direction: HexDirection) -> bool {
+        self.wall_index(cell, direction)
+            .is_some_and(|index| self.walls[index] == Wall::Open)
+    }
+
+    /// Returns the cells which can be reached in a single move from cell
+    pub fn open_neighbors(&self, cell: MazeCell) -> Vec<MazeCell> {
+        HexDirection::ALL
+            .iter()
+            .filter(|&&direction| self.is_open(cell, direction))
+            .filter_map(|&direction| self.neighbor(cell, direction))
+            .collect()
+    }
+
+    /// Returns an SVG document drawing every closed wall, where size is the distance
+    /// from the center of a cell to each of its corners
+    /// Cell (0, 0) is drawn at the bottom left of the image.
+    pub fn to_svg(&self, size: f64) -> String {
+        let cell_width = 3f64.sqrt() * size;
+        let image_width = cell_width * (f64::from(self.width) + 0.5);
+        let image_height = size * (1.5 * f64::from(self.height) + 0.5);
+
+        let mut svg = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{:.2}" height="{:.2}">{}"#,
+            image_width, image_height, LINE_ENDING
+        );
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let cell = MazeCell::new(x, y);
+                let center_x = cell_width * (f64::from(x) + 0.5 + f64::from(y % 2) / 2.0);
+                let center_y = image_height - size * (1.0 + 1.5 * f64::from(y));
+                // corners clockwise from the top, with y increasing down the image
+                let corner = |i: u32| {
+                    let angle = (f64::from(i) * 60.0 - 90.0).to_radians();
+                    (center_x + size * angle.cos(), center_y + size * angle.sin())
+                };
+
+                for &direction in &HexDirection::ALL {
+                    // shared walls are drawn by the cell which stores them
+                    let stored_here = match direction {
+                        HexDirection::East | HexDirection::NorthEast | HexDirection::NorthWest => {
+                            true
+                        }
+                        _ => self.neighbor(cell, direction).is_none(),
+                    };
+                    if !stored_here || self.is_open(cell, direction) {
+                        continue;
+                    }
+
+                    let (from, to) = match direction {
+                        HexDirection::NorthEast => (corner(0), corner(1)),
+                        HexDirection::East => (corner(1), corner(2)),
+                        HexDirection::SouthEast => (corner(2), corner(3)),
+                        HexDirection::SouthWest => (corner(3), corner(4)),
+                        HexDirection::West => (corner(4), corner(5)),
+                        HexDirection::NorthWest => (corner(5), corner(0)),
+                    };
+                    svg.push_str(&format!(
+                        r#"<path d="M{:.2} {:.2} L{:.2} {:.2}" stroke="black" fill="none"/>{}"#,
+                        from.0, from.1, to.0, to.1, LINE_ENDING
+                    ));
+                }
+            }
+        }
+        svg.push_str("</svg>");
+
+        svg
+    }
+
+    fn cell_index(&self, cell: MazeCell) -> usize {
+        (cell.x + cell.y * self.width) as usize
+    }
+
+    /// Returns None if there is no cell on that side of cell
+    fn wall_index(&self, cell: MazeCell, direction: HexDirection) -> Option<usize> {
+        let neighbor = self.neighbor(cell, direction)?;
+        let (owner, offset) = match direction {
+            HexDirection::East => (cell, 0),
+            HexDirection::NorthEast => (cell, 1),
+            HexDirection::NorthWest => (cell, 2),
+            HexDirection::West => (neighbor, 0),
+            HexDirection::SouthWest => (neighbor, 1),
+            HexDirection::SouthEast => (neighbor, 2),
+        };
+
+        Some(self.cell_index(owner) * 3 + offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn neighbors_are_mutual() {
+        let maze = HexMaze::new(4, 4);
+        let opposite = [
+            (HexDirection::East, HexDirection::West),
+            (HexDirection::NorthEast, HexDirection::SouthWest),
+            (HexDirection::NorthWest, HexDirection::SouthEast),
+        ];
+
+        for y in 0..4 {
+            for x in 0..4 {
+                let cell = MazeCell::new(x, y);
+                for &(direction, back) in &opposite {
+                    if let Some(neighbor) = maze.neighbor(cell, direction) {
+                        assert_eq!(Some(cell), maze.neighbor(neighbor, back));
+                        assert_eq!(
+                            maze.wall_index(cell, direction),
+                            maze.wall_index(neighbor, back)
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn recursive_backtracker_is_connected() {
+        let maze = HexMaze::recursive_backtracker_seeded(7, 5, 3);
+
+        let mut reached = vec![MazeCell::new(0, 0)];
+        let mut frontier = vec![MazeCell::new(0, 0)];
+        while let Some(cell) = frontier.pop() {
+            for neighbor in maze.open_neighbors(cell) {
+                if !reached.contains(&neighbor) {
+                    reached.push(neighbor);
+                    frontier.push(neighbor);
+                }
+            }
+        }
+
+        assert_eq!(7 * 5, reached.len());
+    }
+
+    #[test]
+    fn to_svg_single_cell_draws_six_walls() {
+        let maze = HexMaze::new(1, 1);
+
+        assert_eq!(6, maze.to_svg(10.0).matches("<path").count());
+    }
+}
@@ -0,0 +1,176 @@
+/// Colors and sizes used when drawing a maze as an image
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct ImageStyle {
+    /// Red, green, and blue
+    pub wall_color: [u8; 3],
+    /// Red, green, and blue
+    pub background_color: [u8; 3],
+    /// The distance in pixels between the centers of neighboring walls
+    pub cell_size: u32,
+    /// May be fractional, in which case the wall edges are blended into the background
+    pub wall_thickness: f64,
+}
+
+impl Default for ImageStyle {
+    fn default() -> Self {
+        ImageStyle {
+            wall_color: [0, 0, 0],
+            background_color: [255, 255, 255],
+            cell_size: 16,
+            wall_thickness: 2.0,
+        }
+    }
+}
+
+/// An RGB image, stored row by row from the top left pixel
+#[derive(Debug, PartialEq, Clone)]
+pub struct Image {
+    width: u32,
+    height: u32,
+    pixels: Vec<[u8; 3]>,
+}
+
+impl Image {
+    /// Create an image filled with a single color
+    pub fn new(width: u32, height: u32, color: [u8; 3]) -> Self {
+        Image {
+            width,
+            height,
+            pixels: vec![color; (width * height) as usize],
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns the color of the pixel x columns from the left and y rows from the top
+    /// Panics if the pixel is outside of the image
+    pub fn pixel(&self, x: u32, y: u32) -> [u8; 3] {
+        assert!(x < self.width && y < self.height);
+        self.pixels[(x + y * self.width) as usize]
+    }
+
+    /// Blends color over the existing pixel, where coverage from 0 to 1 is how much of
+    /// the pixel the new color covers
+    pub(crate) fn blend(&mut self, x: u32, y: u32, color: [u8; 3], coverage: f64) {
+        let pixel = &mut self.pixels[(x + y * self.width) as usize];
+        for (channel, &target) in pixel.iter_mut().zip(&color) {
+            let blended =
+                f64::from(*channel) + (f64::from(target) - f64::from(*channel)) * coverage;
+            *channel = blended.round() as u8;
+        }
+    }
+
+    /// Encodes the image as an uncompressed PNG file
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut header = vec![];
+        header.extend_from_slice(&self.width.to_be_bytes());
+        header.extend_from_slice(&self.height.to_be_bytes());
+        // 8 bits per channel, RGB, default compression, filtering, and no interlacing
+        header.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+        // each row starts with a byte choosing the filter, where 0 is no filter
+        let mut raw = Vec::with_capacity(((self.width * 3 + 1) * self.height) as usize);
+        for row in self.pixels.chunks(self.width.max(1) as usize) {
+            raw.push(0);
+            for pixel in row {
+                raw.extend_from_slice(pixel);
+            }
+        }
+
+        let mut png = vec![0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+        write_chunk(&mut png, b"IHDR", &header);
+        write_chunk(&mut png, b"IDAT", &zlib_stored(&raw));
+        write_chunk(&mut png, b"IEND", &[]);
+
+        png
+    }
+}
+
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// Wraps data in a zlib stream made of uncompressed deflate blocks
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut stream = vec![0x78, 0x01];
+    let mut blocks = data.chunks(0xffff).peekable();
+    if blocks.peek().is_none() {
+        // an empty final block
+        stream.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let is_final = blocks.peek().is_none();
+        stream.push(u8::from(is_final));
+        let len = block.len() as u16;
+        stream.extend_from_slice(&len.to_le_bytes());
+        stream.extend_from_slice(&(!len).to_le_bytes());
+        stream.extend_from_slice(block);
+    }
+    stream.extend_from_slice(&adler32(data).to_be_bytes());
+
+    stream
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + u32::from(byte)) % 65521;
+        b = (b + a) % 65521;
+    }
+
+    (b << 16) | a
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffffu32;
+    for &byte in data {
+        crc ^= u32::from(byte);
+        for _bit in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crc32_check_value() {
+        assert_eq!(0xcbf4_3926, crc32(b"123456789"));
+    }
+
+    #[test]
+    fn adler32_check_value() {
+        assert_eq!(0x11e6_0398, adler32(b"Wikipedia"));
+    }
+
+    #[test]
+    fn to_png_layout() {
+        let png = Image::new(2, 1, [255, 0, 0]).to_png();
+
+        assert_eq!(b"\x89PNG\r\n\x1a\n", &png[..8]);
+        assert_eq!(b"IHDR", &png[12..16]);
+        assert_eq!(b"IEND", &png[png.len() - 8..png.len() - 4]);
+        // the stored block holds the row's filter byte followed by the two red pixels
+        let raw = [0, 255, 0, 0, 255, 0, 0];
+        assert!(png.windows(raw.len()).any(|window| window == raw));
+    }
+}
@@ -1,35 +1,62 @@
-use rand::Rng as _;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom as _;
+use rand::{Rng as _, SeedableRng as _};
+use std::cell::OnceCell;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet, VecDeque};
 use std::fmt;
+use std::io::{self, BufWriter, Write as _};
 
-/// +---+---+---+
-/// | 02| 12| 22|
-/// +---+---+---+
-/// | 01| 11| 21|
-/// +---+---+---+
-/// | 00| 10| 20|
-/// +---+---+---+
-///
-/// Cell positions labeled above as xy
-/// Walls marked below increasing from 0..N
-///
-/// Vertical Walls
-/// 8 11
-/// 7 10
-/// 6 9
-///
-/// Horizontal Walls
-/// 3 4 5
-/// 0 1 2
-///
+mod const_maze;
+mod hex_maze;
+mod image;
+mod mask;
+
+pub use const_maze::ConstMaze;
+pub use hex_maze::{HexDirection, HexMaze};
+pub use image::{Image, ImageStyle};
+pub use mask::Mask;
+
+// +---+---+---+
+// | 02| 12| 22|
+// +---+---+---+
+// | 01| 11| 21|
+// +---+---+---+
+// | 00| 10| 20|
+// +---+---+---+
+//
+// Cell positions labeled above as xy
+// Walls marked below increasing from 0..N
+//
+// Vertical Walls
+// 8 11
+// 7 10
+// 6 9
+//
+// Horizontal Walls
+// 3 4 5
+// 0 1 2
 
 const LINE_ENDING: &str = "\n";
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 enum Wall {
     Open,
     Closed,
 }
 
+/// The reason a wall could not be opened
+#[derive(Debug, PartialEq)]
+enum WallError {
+    /// The cell is in the top row, so its north wall is the maze edge
+    AtNorthEdge,
+    /// The cell is in the right-most column, so its east wall is the maze edge
+    AtEastEdge,
+    /// The cell is outside of the maze
+    OutOfBounds,
+}
+
+#[derive(Debug, PartialEq, Clone)]
 pub struct Maze {
     height: u32,
     width: u32,
@@ -43,10 +70,67 @@ struct MazeIterator {
     max_y: u32,
 }
 
+/// A source of randomness for maze generation
+pub trait MazeRng {
+    fn next_bool(&mut self) -> bool;
+    /// Returns an index in the range 0..n
+    fn next_index(&mut self, n: usize) -> usize;
+}
+
+/// Adapts any random number generator from the rand crate for maze generation
+pub struct RandRng<R>(pub R);
+
+/// Picks run indices for sidewinder_seeded the way it did before MazeRng existed, by
+/// taking a random usize modulo the run length, so existing seeds keep their mazes
+struct SeededSidewinderRng(StdRng);
+
+/// Generates a maze using the recursive backtracker algorithm one carve at a time,
+/// which allows the generation to be observed or animated
+pub struct RecursiveBacktrackerGen<R = RandRng<StdRng>> {
+    maze: Maze,
+    rng: R,
+    visited: Vec<bool>,
+    stack: Vec<MazeCell>,
+}
+
 /// Calculates and stores the distance from start point to every other cell on the maze
+/// Both distances and parents are indexed as [x][y], and are None for unreachable cells
 struct MazePath {
     start: MazeCell,
-    distances: Vec<Vec<u32>>,
+    distances: Vec<Vec<Option<u32>>>,
+    /// The previous cell on the shortest path from start to each cell
+    parents: Vec<Vec<Option<MazeCell>>>,
+    /// Every reachable cell in the order the flood fill reached it
+    visit_order: Vec<MazeCell>,
+}
+
+/// Runs a breadth first search one expansion at a time, so the progress of the search
+/// can be shown as it happens
+pub struct BfsSolver<'a> {
+    maze: &'a Maze,
+    goal: MazeCell,
+    /// Indexed as [x][y], and None for cells which haven't been reached yet
+    distances: Vec<Vec<Option<u32>>>,
+    frontier: VecDeque<MazeCell>,
+}
+
+/// The result of a single `BfsSolver` expansion
+#[derive(Debug, PartialEq)]
+pub struct SolverStep {
+    /// The cells reached for the first time by this step
+    pub visited: Vec<MazeCell>,
+    /// True once the goal has been reached, by this step or an earlier one
+    pub found_goal: bool,
+}
+
+/// Answers repeated distance and path queries from a single start cell, flooding the
+/// maze only once on the first query
+pub struct SolvedMaze<'a> {
+    maze: &'a Maze,
+    start: MazeCell,
+    path: OnceCell<MazePath>,
+    #[cfg(test)]
+    fill_count: std::cell::Cell<u32>,
 }
 
 /// Stores the available movement options from a given starting point
@@ -59,10 +143,134 @@ struct MovementOptions {
     west: Option<MazeCell>,
 }
 
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct MazeCell {
+    pub x: u32,
+    pub y: u32,
+}
+
 #[derive(Copy, Clone, PartialEq, Debug)]
-struct MazeCell {
-    x: u32,
-    y: u32,
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+/// The rotations and reflections of a rectangular grid
+/// Rotations are counterclockwise
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Symmetry {
+    Identity,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    /// Swaps the left and right sides
+    MirrorHorizontal,
+    /// Swaps the top and bottom
+    MirrorVertical,
+    /// Reflects across the diagonal through the bottom left corner
+    MirrorDiagonal,
+    /// Reflects across the diagonal through the top left corner
+    MirrorAntiDiagonal,
+}
+
+/// A rectangular region of cells whose bottom left cell is (x, y)
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Rect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// The maze generation algorithms supported by this crate
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Algorithm {
+    BinaryTree,
+    Sidewinder,
+    RecursiveBacktracker,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum MazeError {
+    /// The cell lies outside of the maze
+    CellOutOfBounds(MazeCell),
+    /// The cells do not share a wall
+    NotAdjacent(MazeCell, MazeCell),
+    /// The rectangle is empty or extends beyond the maze
+    RectOutOfBounds(Rect),
+    /// The rectangles share at least one cell
+    RectsOverlap(Rect, Rect),
+    /// The cell is not covered by any of the given rectangles
+    CellNotCovered(MazeCell),
+    /// The cell appears more than once in a path
+    CellRepeated(MazeCell),
+    /// The passage between the cells closes a loop
+    ClosesLoop(MazeCell, MazeCell),
+    /// The width or height, given in that order, is zero
+    EmptyDimensions(u32, u32),
+    /// The maze has the wrong number of walls for its size, as (expected, actual)
+    WallCountMismatch(usize, usize),
+    /// A block grid column count or column length, given in that order, is not an odd
+    /// number of at least three
+    InvalidBlockGrid(usize, usize),
+    /// The center of the cell is marked as a wall in a block grid
+    CellCenterBlocked(MazeCell),
+    /// Two mazes which must be the same size are not, given as (width, height) of each
+    SizeMismatch((u32, u32), (u32, u32)),
+}
+
+/// The reasons a maze can't be solved between two cells
+#[derive(Debug, PartialEq)]
+pub enum SolveError {
+    StartOutOfBounds(MazeCell),
+    EndOutOfBounds(MazeCell),
+    /// No sequence of open walls joins the start to the end
+    Unreachable,
+}
+
+/// Errors from parsing a maze drawn as text
+#[derive(Debug, PartialEq)]
+pub enum ParseError {
+    /// The text has fewer lines or columns than a single cell needs
+    TooSmall,
+    /// The line, counting from zero at the top, has a different length to the first line
+    RaggedLine(usize),
+    /// The line and column count can't be divided evenly into cells
+    UnevenGrid,
+    /// The character at the given line and column isn't a wall or a passage
+    UnexpectedChar(char, usize, usize),
+    /// The width and height are missing, malformed, or zero
+    InvalidDimensions,
+    /// The text describes the wrong number of walls for the maze size, as
+    /// (expected, actual)
+    WrongWallCount(usize, usize),
+    /// The run length starting at the given column is zero, too large, or isn't
+    /// followed by a wall
+    InvalidRun(usize),
+}
+
+/// The box drawing characters used when rendering a maze
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum RenderStyle {
+    /// Square corners, as used by the Display implementation
+    Sharp,
+    /// Rounded corners wherever a wall turns
+    Rounded,
+}
+
+/// The characters used to mark the start and end cells of a rendered maze
+pub struct EndpointMarkers {
+    pub start: char,
+    pub end: char,
+    /// Used instead when the start and end are the same cell
+    pub both: char,
+}
+
+/// Tracks which items have been joined together, used to avoid creating loops
+struct DisjointSet {
+    parents: Vec<usize>,
 }
 
 impl Maze {
@@ -81,68 +289,106 @@ impl Maze {
         }
     }
 
+    /// Checks that the maze has at least one cell and that its wall storage matches
+    /// its size, so that indexing into the walls can't panic
+    pub fn validate(&self) -> Result<(), MazeError> {
+        if self.width == 0 || self.height == 0 {
+            return Err(MazeError::EmptyDimensions(self.width, self.height));
+        }
+
+        let (width, height) = (self.width as usize, self.height as usize);
+        let expected = (width - 1) * height + (height - 1) * width;
+        if self.walls.len() != expected {
+            return Err(MazeError::WallCountMismatch(expected, self.walls.len()));
+        }
+
+        Ok(())
+    }
+
+    /// Closes every wall, returning the maze to the state it was in when created
+    /// The existing wall storage is reused rather than reallocated
+    pub fn reset(&mut self) {
+        for wall in self.walls.iter_mut() {
+            *wall = Wall::Closed;
+        }
+    }
+
+    /// Uses the seed in the MAZE_SEED environment variable when it is set, which makes
+    /// it possible to reproduce a maze while debugging
     pub fn binary_tree(width: u32, height: u32) -> Self {
-        let mut rng = rand::thread_rng();
-        Self::binary_tree_with_rand_fn(height, width, || rng.gen_bool(0.5))
+        match seed_from_env() {
+            Some(seed) => Self::binary_tree_seeded(width, height, seed),
+            None => Self::binary_tree_with_rng(width, height, &mut RandRng(rand::thread_rng())),
+        }
     }
 
-    fn binary_tree_with_rand_fn<F>(width: u32, height: u32, mut rand_bool: F) -> Self
-    where
-        F: FnMut() -> bool,
-    {
-        let mut maze = Self::new(height, width);
+    pub fn binary_tree_seeded(width: u32, height: u32, seed: u64) -> Self {
+        Self::binary_tree_with_rng(width, height, &mut RandRng(StdRng::seed_from_u64(seed)))
+    }
+
+    pub fn binary_tree_with_rng(width: u32, height: u32, rng: &mut impl MazeRng) -> Self {
+        Self::binary_tree_config(width, height, Direction::North, Direction::East, rng)
+    }
+
+    /// Generates a binary tree maze which carves toward the given pair of directions
+    /// rather than north and east
+    /// Each cell carves toward primary when the rng returns true and toward fallback
+    /// otherwise, and falls back to the other direction at the maze edge. The two
+    /// directions must be perpendicular for the maze to be perfect.
+    pub fn binary_tree_config(
+        width: u32,
+        height: u32,
+        primary: Direction,
+        fallback: Direction,
+        rng: &mut impl MazeRng,
+    ) -> Self {
+        let mut maze = Self::new(width, height);
         let maze_iter = MazeIterator::new(&maze);
         for cell in maze_iter {
-            if rand_bool() {
-                let result = maze.open_north_wall(cell);
-                if result.is_err() {
-                    // if you can't open the north wall, fall back to opening the east wall
-                    // the northeast cell will fail opening both north and east walls, so we ignore this error result
-                    let _ = maze.open_east_wall(cell);
-                }
+            let (first, second) = if rng.next_bool() {
+                (primary, fallback)
             } else {
-                let result = maze.open_east_wall(cell);
-                if result.is_err() {
-                    // if you can't open the east wall, fall back to opening the north wall
-                    // the northeast cell will fail opening both north and east walls, so we ignore this error result
-                    let _ = maze.open_north_wall(cell);
-                }
+                (fallback, primary)
+            };
+            // the corner cell can't carve in either direction, so it is left alone
+            if !maze.open_wall_toward(cell, first) {
+                maze.open_wall_toward(cell, second);
             }
         }
 
         maze
     }
 
+    /// Uses the seed in the MAZE_SEED environment variable when it is set, which makes
+    /// it possible to reproduce a maze while debugging
     pub fn sidewinder(width: u32, height: u32) -> Self {
-        let mut rng = rand::thread_rng();
-        let mut rng2 = rand::thread_rng();
-        Self::sidewinder_with_rand_fn(height, width, || rng.gen_bool(0.5), || rng2.gen())
+        match seed_from_env() {
+            Some(seed) => Self::sidewinder_seeded(width, height, seed),
+            None => Self::sidewinder_with_rng(width, height, &mut RandRng(rand::thread_rng())),
+        }
     }
 
-    fn sidewinder_with_rand_fn<F1, F2>(
-        width: u32,
-        height: u32,
-        mut rand_bool: F1,
-        mut rand_usize: F2,
-    ) -> Self
-    where
-        F1: FnMut() -> bool,
-        F2: FnMut() -> usize,
-    {
-        let mut maze = Self::new(height, width);
+    pub fn sidewinder_seeded(width: u32, height: u32, seed: u64) -> Self {
+        let mut rng = SeededSidewinderRng(StdRng::seed_from_u64(seed));
+        Self::sidewinder_with_rng(width, height, &mut rng)
+    }
+
+    pub fn sidewinder_with_rng(width: u32, height: u32, rng: &mut impl MazeRng) -> Self {
+        let mut maze = Self::new(width, height);
         let maze_iter = MazeIterator::new(&maze);
         let mut cells_in_run = vec![];
         for cell in maze_iter {
             cells_in_run.push(cell);
-            if rand_bool() {
+            if rng.next_bool() {
                 // randomly open a passage north from one of the cells in run
-                let selected_cell_index = rand_usize() % cells_in_run.len();
+                let selected_cell_index = rng.next_index(cells_in_run.len());
                 let selected_cell = cells_in_run[selected_cell_index];
                 cells_in_run.drain(..); // the run ends once a passage is opened north
                 let result = maze.open_north_wall(selected_cell);
                 if result.is_err() {
-                    // if you can't open the north wall, fall back to opening the east wall
-                    let _ = maze.open_east_wall(selected_cell);
+                    // the top row can't open north, so fall back to continuing the run east
+                    // the previous cells in the run are already joined by their east walls
+                    let _ = maze.open_east_wall(cell);
                 }
             } else {
                 let result = maze.open_east_wall(cell);
@@ -158,6 +404,250 @@ impl Maze {
         maze
     }
 
+    pub fn recursive_backtracker(width: u32, height: u32) -> Self {
+        Self::recursive_backtracker_with_rng(width, height, &mut RandRng(rand::thread_rng()))
+    }
+
+    pub fn recursive_backtracker_seeded(width: u32, height: u32, seed: u64) -> Self {
+        RecursiveBacktrackerGen::new(width, height, seed).finish()
+    }
+
+    pub fn recursive_backtracker_with_rng(width: u32, height: u32, rng: &mut impl MazeRng) -> Self {
+        RecursiveBacktrackerGen::with_rng(width, height, rng).finish()
+    }
+
+    /// Carves the given path, then grows a perfect maze out from it with the recursive
+    /// backtracker, so the path is the only route between its first and last cells
+    /// Errors if any cell is outside the maze, any two consecutive cells aren't
+    /// adjacent, or the path visits a cell more than once.
+    pub fn generate_around_path(
+        width: u32,
+        height: u32,
+        path: &[MazeCell],
+        seed: u64,
+    ) -> Result<Maze, MazeError> {
+        let mut maze = Self::new(width, height);
+        for (i, &cell) in path.iter().enumerate() {
+            if !maze.contains(cell) {
+                return Err(MazeError::CellOutOfBounds(cell));
+            }
+            if path[..i].contains(&cell) {
+                return Err(MazeError::CellRepeated(cell));
+            }
+        }
+        let edges: Vec<(MazeCell, MazeCell)> =
+            path.windows(2).map(|pair| (pair[0], pair[1])).collect();
+        maze.carve_edges(&edges)?;
+
+        if path.is_empty() {
+            return Ok(Self::recursive_backtracker_seeded(width, height, seed));
+        }
+        let rng = RandRng(StdRng::seed_from_u64(seed));
+        Ok(RecursiveBacktrackerGen::resume(maze, path, rng).finish())
+    }
+
+    /// Generates a maze whose solution between the first and last cells of shape
+    /// traces exactly that shape, such as a zigzag or a spiral
+    /// Since the maze is perfect the shape is the only route between its endpoints, not
+    /// just the shortest. Errors as `generate_around_path` does.
+    pub fn generate_with_path_shape(
+        width: u32,
+        height: u32,
+        shape: &[MazeCell],
+        seed: u64,
+    ) -> Result<Maze, MazeError> {
+        Self::generate_around_path(width, height, shape, seed)
+    }
+
+    /// Carves a perfect maze by visiting the cells in the given order, connecting each
+    /// cell to its visited neighbors, checked north, east, south, west, wherever they
+    /// aren't already connected to it
+    /// Errors if order isn't made up of every cell of the maze exactly once.
+    pub fn carve_by_order(width: u32, height: u32, order: &[MazeCell]) -> Result<Maze, MazeError> {
+        let mut maze = Self::new(width, height);
+        let mut visited = vec![false; (width * height) as usize];
+        let mut connected_cells = DisjointSet::new((width * height) as usize);
+
+        for &cell in order {
+            if !maze.contains(cell) {
+                return Err(MazeError::CellOutOfBounds(cell));
+            }
+            if visited[maze.cell_index(cell)] {
+                return Err(MazeError::CellRepeated(cell));
+            }
+            visited[maze.cell_index(cell)] = true;
+
+            for neighbor in maze.neighbors_of(cell) {
+                let neighbor_index = maze.cell_index(neighbor);
+                if visited[neighbor_index]
+                    && connected_cells.union(maze.cell_index(cell), neighbor_index)
+                {
+                    maze.open_wall_between(cell, neighbor);
+                }
+            }
+        }
+
+        if let Some(cell) = MazeIterator::new(&maze).find(|&cell| !visited[maze.cell_index(cell)]) {
+            return Err(MazeError::CellNotCovered(cell));
+        }
+
+        Ok(maze)
+    }
+
+    /// Completes a partly carved template into a perfect maze, keeping every passage
+    /// already open in the template
+    /// The passages of a maze generated by algorithm are added wherever they don't
+    /// close a loop, and any cells still cut off are then joined through randomly
+    /// chosen walls. Errors if the template's own passages already form a loop.
+    pub fn complete(template: Maze, algorithm: Algorithm, seed: u64) -> Result<Maze, MazeError> {
+        let mut maze = template;
+        let mut connected_cells = DisjointSet::new((maze.width * maze.height) as usize);
+        for index in 0..maze.walls.len() {
+            if maze.walls[index] == Wall::Open {
+                let (a, b) = maze.cells_for_wall(index);
+                if !connected_cells.union(maze.cell_index(a), maze.cell_index(b)) {
+                    return Err(MazeError::ClosesLoop(a, b));
+                }
+            }
+        }
+
+        let generated = algorithm.generate_seeded(maze.width, maze.height, seed);
+        let generated_walls =
+            (0..maze.walls.len()).filter(|&index| generated.walls[index] == Wall::Open);
+        let mut remaining_walls: Vec<usize> = (0..maze.walls.len()).collect();
+        remaining_walls.shuffle(&mut StdRng::seed_from_u64(seed));
+        for index in generated_walls.chain(remaining_walls) {
+            let (a, b) = maze.cells_for_wall(index);
+            if connected_cells.union(maze.cell_index(a), maze.cell_index(b)) {
+                maze.walls[index] = Wall::Open;
+            }
+        }
+
+        Ok(maze)
+    }
+
+    /// Generates each zone independently with its own algorithm, then opens a single
+    /// passage between adjacent zones so the whole maze is connected without loops
+    /// The zones must not overlap and must together cover every cell of the maze
+    /// The same seed always produces the same maze, with each zone generated from
+    /// its own seed counting up from the given seed.
+    pub fn from_zones(
+        width: u32,
+        height: u32,
+        zones: Vec<(Rect, Algorithm)>,
+        seed: u64,
+    ) -> Result<Maze, MazeError> {
+        let mut maze = Self::new(width, height);
+
+        for (i, &(rect, _algorithm)) in zones.iter().enumerate() {
+            maze.check_rect(rect)?;
+            if let Some(&(other, _algorithm)) = zones[..i]
+                .iter()
+                .find(|(other, _algorithm)| rect.overlaps(other))
+            {
+                return Err(MazeError::RectsOverlap(other, rect));
+            }
+        }
+        if let Some(cell) = MazeIterator::new(&maze)
+            .find(|&cell| !zones.iter().any(|(rect, _algorithm)| rect.contains(cell)))
+        {
+            return Err(MazeError::CellNotCovered(cell));
+        }
+
+        for (i, &(rect, algorithm)) in zones.iter().enumerate() {
+            let zone_seed = seed.wrapping_add(i as u64);
+            let zone = algorithm.generate_seeded(rect.width, rect.height, zone_seed);
+            maze.paste(&zone, rect.x, rect.y);
+        }
+
+        // connect the zones along a spanning tree so no loops are introduced
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut connected_zones = DisjointSet::new(zones.len());
+        for (i, &(a, _algorithm)) in zones.iter().enumerate() {
+            for (j, &(b, _algorithm)) in zones.iter().enumerate().skip(i + 1) {
+                let shared_walls: Vec<(MazeCell, MazeCell)> = MazeIterator::new(&maze)
+                    .filter(|&cell| a.contains(cell))
+                    .flat_map(|cell| {
+                        maze.neighbors_of(cell)
+                            .into_iter()
+                            .filter(|&neighbor| b.contains(neighbor))
+                            .map(move |neighbor| (cell, neighbor))
+                    })
+                    .collect();
+
+                if !shared_walls.is_empty() && connected_zones.union(i, j) {
+                    let (cell, neighbor) = shared_walls[rng.gen_range(0, shared_walls.len())];
+                    maze.open_wall_between(cell, neighbor);
+                }
+            }
+        }
+
+        Ok(maze)
+    }
+
+    /// Carves the minimum spanning tree of the grid, where weight_fn gives the cost of
+    /// opening the wall between two adjacent cells
+    /// Random weights give a maze much like Kruskal's algorithm would, while structured
+    /// weights can bias the maze toward long straight or winding passages. Equal
+    /// weights are broken in favor of walls which come first in wall order.
+    pub fn from_weighted_edges(
+        width: u32,
+        height: u32,
+        weight_fn: impl Fn(MazeCell, MazeCell) -> f64,
+    ) -> Maze {
+        let mut maze = Self::new(width, height);
+
+        let mut weighted_walls: Vec<(usize, f64)> = (0..maze.walls.len())
+            .map(|index| {
+                let (a, b) = maze.cells_for_wall(index);
+                (index, weight_fn(a, b))
+            })
+            .collect();
+        weighted_walls.sort_by(|(_a, a_weight), (_b, b_weight)| a_weight.total_cmp(b_weight));
+
+        let mut connected_cells = DisjointSet::new((width * height) as usize);
+        for (index, _weight) in weighted_walls {
+            let (a, b) = maze.cells_for_wall(index);
+            if connected_cells.union(maze.cell_index(a), maze.cell_index(b)) {
+                maze.walls[index] = Wall::Open;
+            }
+        }
+
+        maze
+    }
+
+    /// Generates a perfect maze of concentric rectangular rings, where passages mostly
+    /// follow the rings and only a few lead inward or outward between them
+    /// Built as a weighted spanning tree with random weights, where walls between two
+    /// different rings cost extra to open.
+    pub fn onion(width: u32, height: u32, seed: u64) -> Maze {
+        // how much more a wall between rings costs than a wall along a ring
+        const RADIAL_PENALTY: f64 = 0.75;
+
+        let mut rng = StdRng::seed_from_u64(seed);
+        // one random weight for the north wall and one for the east wall of each cell
+        let noise: Vec<[f64; 2]> = (0..width * height)
+            .map(|_| [rng.gen(), rng.gen()])
+            .collect();
+        let ring = |cell: MazeCell| {
+            cell.x
+                .min(cell.y)
+                .min(width - 1 - cell.x)
+                .min(height - 1 - cell.y)
+        };
+
+        Maze::from_weighted_edges(width, height, |a, b| {
+            let lower = if (a.x, a.y) < (b.x, b.y) { a } else { b };
+            let side = if a.x == b.x { 0 } else { 1 };
+            let weight = noise[(lower.x + lower.y * width) as usize][side];
+            if ring(a) == ring(b) {
+                weight
+            } else {
+                weight + RADIAL_PENALTY
+            }
+        })
+    }
+
     /// Gets the index into the wall array which stores the wall to the north of the
     /// cell at (x, y). Returns None for cells in the top row.
     fn north_wall_index_for_cell(&self, x: u32, y: u32) -> Option<usize> {
@@ -201,7 +691,6 @@ impl Maze {
         }
     }
 
-    #[allow(dead_code)] // keep this method for symmetry although it is currently unused
     fn west_wall_index_for_cell(&self, x: u32, y: u32) -> Option<usize> {
         match (x, y) {
             // walls at left of maze have no west wall
@@ -212,8 +701,12 @@ impl Maze {
     }
 
     /// Returns Ok if it was able to open the wall
-    /// Returns Err if north wall for this cell was the edge of the maze
-    fn open_north_wall(&mut self, cell: MazeCell) -> Result<(), ()> {
+    /// Returns Err if north wall for this cell was the edge of the maze, or if the
+    /// cell is outside of the maze
+    fn open_north_wall(&mut self, cell: MazeCell) -> Result<(), WallError> {
+        if !self.contains(cell) {
+            return Err(WallError::OutOfBounds);
+        }
         let index = self.north_wall_index_for_cell(cell.x, cell.y);
 
         match index {
@@ -221,13 +714,17 @@ impl Maze {
                 self.walls[index] = Wall::Open;
                 Ok(())
             }
-            None => Err(()),
+            None => Err(WallError::AtNorthEdge),
         }
     }
 
     /// Returns Ok if it was able to open the wall
-    /// Returns Err if east wall for this cell was the edge of the maze
-    fn open_east_wall(&mut self, cell: MazeCell) -> Result<(), ()> {
+    /// Returns Err if east wall for this cell was the edge of the maze, or if the
+    /// cell is outside of the maze
+    fn open_east_wall(&mut self, cell: MazeCell) -> Result<(), WallError> {
+        if !self.contains(cell) {
+            return Err(WallError::OutOfBounds);
+        }
         let index = self.east_wall_index_for_cell(cell.x, cell.y);
 
         match index {
@@ -235,410 +732,5430 @@ impl Maze {
                 self.walls[index] = Wall::Open;
                 Ok(())
             }
-            None => Err(()),
+            None => Err(WallError::AtEastEdge),
         }
     }
 
-    fn get_movement_options_for(&self, cell: MazeCell) -> MovementOptions {
-        MovementOptions::new(Some(MazeCell::new(0, 1)), None, None, None)
-    }
-}
+    /// Opens the wall shared by two orthogonally adjacent cells
+    pub fn connect(&mut self, a: MazeCell, b: MazeCell) -> Result<(), MazeError> {
+        let index = self.checked_wall_index_between(a, b)?;
+        self.walls[index] = Wall::Open;
 
-impl fmt::Display for Maze {
-    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        let horizontal_wall_segment = "───";
-        let vertical_wall_segment = "│";
-        let nowall_segment = "   ";
+        Ok(())
+    }
 
-        let mut total = String::from("┌");
+    /// Opens the wall on the given side of the cell
+    /// Returns false without opening anything if that side is the edge of the maze
+    fn open_wall_toward(&mut self, cell: MazeCell, direction: Direction) -> bool {
+        let MazeCell { x, y } = cell;
+        let index = match direction {
+            Direction::North => self.north_wall_index_for_cell(x, y),
+            Direction::East => self.east_wall_index_for_cell(x, y),
+            Direction::South => self.south_wall_index_for_cell(x, y),
+            Direction::West => self.west_wall_index_for_cell(x, y),
+        };
 
-        // the top maze edge
-        for x in 1..(self.width + 1) {
-            total += horizontal_wall_segment;
-            total += get_corner(self, x, self.height).unwrap();
+        match index {
+            Some(index) => {
+                self.walls[index] = Wall::Open;
+                true
+            }
+            None => false,
         }
+    }
 
-        for y in (0..self.height).rev() {
-            total += LINE_ENDING;
+    /// Opens each wall between the given pairs of cells
+    /// No walls are opened if any pair is out of bounds or not orthogonally adjacent
+    pub fn carve_edges(&mut self, edges: &[(MazeCell, MazeCell)]) -> Result<(), MazeError> {
+        let mut indexes = Vec::with_capacity(edges.len());
+        for &(a, b) in edges {
+            indexes.push(self.checked_wall_index_between(a, b)?);
+        }
 
-            // add left maze edge
-            total += vertical_wall_segment;
+        for index in indexes {
+            self.walls[index] = Wall::Open;
+        }
 
-            // for each cell add east wall
-            for x in 0..self.width {
-                total += nowall_segment;
+        Ok(())
+    }
 
-                if let Some(index) = self.east_wall_index_for_cell(x, y) {
-                    total += match self.walls[index] {
-                        Wall::Open => " ",
-                        Wall::Closed => vertical_wall_segment,
-                    };
-                } else {
-                    // you've reached the edge of the maze
-                    total += vertical_wall_segment;
-                }
+    fn checked_wall_index_between(&self, a: MazeCell, b: MazeCell) -> Result<usize, MazeError> {
+        for &cell in &[a, b] {
+            if !self.contains(cell) {
+                return Err(MazeError::CellOutOfBounds(cell));
             }
+        }
 
-            // insert newline between vertical walls and horizontal walls
-            total += LINE_ENDING;
+        self.wall_index_between(a, b)
+            .ok_or(MazeError::NotAdjacent(a, b))
+    }
 
-            total += get_corner(self, 0, y).unwrap();
+    /// Gets the index into the wall array of the wall shared by two cells
+    /// Returns None if the cells are not orthogonally adjacent
+    fn wall_index_between(&self, a: MazeCell, b: MazeCell) -> Option<usize> {
+        if a.x == b.x && a.y + 1 == b.y {
+            self.north_wall_index_for_cell(a.x, a.y)
+        } else if a.x == b.x && b.y + 1 == a.y {
+            self.north_wall_index_for_cell(b.x, b.y)
+        } else if a.y == b.y && a.x + 1 == b.x {
+            self.east_wall_index_for_cell(a.x, a.y)
+        } else if a.y == b.y && b.x + 1 == a.x {
+            self.east_wall_index_for_cell(b.x, b.y)
+        } else {
+            None
+        }
+    }
 
-            for x in 0..self.width {
-                // for each cell add south wall
-                let wall_index = self.south_wall_index_for_cell(x, y);
-
-                if let Some(index) = wall_index {
-                    total += match self.walls[index] {
-                        Wall::Open => nowall_segment,
-                        Wall::Closed => horizontal_wall_segment,
-                    };
-                } else {
-                    // you've reached the edge of the maze
-                    total += horizontal_wall_segment;
-                }
-                total += get_corner(self, x + 1, y).unwrap();
-            }
+    /// Opens the wall shared by two orthogonally adjacent cells
+    fn open_wall_between(&mut self, a: MazeCell, b: MazeCell) {
+        let index = self.wall_index_between(a, b);
+        debug_assert!(index.is_some(), "cells must be adjacent");
+        if let Some(index) = index {
+            self.walls[index] = Wall::Open;
         }
+    }
 
-        write!(f, "{}", total)
+    /// Returns true if the cell lies within the maze
+    fn contains(&self, cell: MazeCell) -> bool {
+        cell.x < self.width && cell.y < self.height
     }
-}
 
-fn get_corner(maze: &Maze, x: u32, y: u32) -> Option<&'static str> {
-    use Wall::*;
-    if x > maze.width || y > maze.height {
-        return None;
-    }
-    let corner = match (x, y) {
-        (0, 0) => "└",
-        (0, y) if y == maze.height => "┌",
-        (x, 0) if x == maze.width => "┘",
-        (x, y) if x == maze.width && y == maze.height => "┐",
-        (0, y) => match maze.walls[maze.south_wall_index_for_cell(0, y).unwrap()] {
-            Open => "│",
-            Closed => "├",
-        },
-        (x, y) if x == maze.width => {
-            match maze.walls[maze.south_wall_index_for_cell(x - 1, y).unwrap()] {
-                Open => "│",
-                Closed => "┤",
-            }
-        }
-        (x, 0) => match maze.walls[maze.east_wall_index_for_cell(x - 1, 0).unwrap()] {
-            Open => "─",
-            Closed => "┴",
-        },
-        (x, y) if y == maze.height => {
-            match maze.walls[maze.east_wall_index_for_cell(x - 1, y - 1).unwrap()] {
-                Open => "─",
-                Closed => "┬",
-            }
-        }
-        (x, y) => {
-            let walls = (
-                maze.walls[maze.east_wall_index_for_cell(x - 1, y).unwrap()],
-                maze.walls[maze.north_wall_index_for_cell(x, y - 1).unwrap()],
-                maze.walls[maze.east_wall_index_for_cell(x - 1, y - 1).unwrap()],
-                maze.walls[maze.north_wall_index_for_cell(x - 1, y - 1).unwrap()],
-            );
-            match walls {
-                (Open, Open, Open, Open) => " ",
-                (Open, Open, Open, Closed) => "╴",
-                (Open, Open, Closed, Open) => "╷",
-                (Open, Open, Closed, Closed) => "┐",
-                (Open, Closed, Open, Open) => "╶",
-                (Open, Closed, Open, Closed) => "─",
-                (Open, Closed, Closed, Open) => "┌",
-                (Open, Closed, Closed, Closed) => "┬",
-                (Closed, Open, Open, Open) => "╵",
-                (Closed, Open, Open, Closed) => "┘",
-                (Closed, Open, Closed, Open) => "│",
-                (Closed, Open, Closed, Closed) => "┤",
-                (Closed, Closed, Open, Open) => "└",
-                (Closed, Closed, Open, Closed) => "┴",
-                (Closed, Closed, Closed, Open) => "├",
-                (Closed, Closed, Closed, Closed) => "┼",
-            }
+    /// Returns the cells orthogonally adjacent to the given cell, ignoring walls
+    fn neighbors_of(&self, cell: MazeCell) -> Vec<MazeCell> {
+        let MazeCell { x, y } = cell;
+        let mut neighbors = vec![];
+        if y + 1 < self.height {
+            neighbors.push(MazeCell::new(x, y + 1));
         }
-    };
-    Some(corner)
-}
-
-impl MazeIterator {
-    fn new(maze: &Maze) -> Self {
-        MazeIterator {
-            current_x: 0,
-            current_y: 0,
-            max_x: maze.width - 1,
-            max_y: maze.height - 1,
+        if x + 1 < self.width {
+            neighbors.push(MazeCell::new(x + 1, y));
+        }
+        if y > 0 {
+            neighbors.push(MazeCell::new(x, y - 1));
         }
+        if x > 0 {
+            neighbors.push(MazeCell::new(x - 1, y));
+        }
+
+        neighbors
     }
-}
 
-impl Iterator for MazeIterator {
-    type Item = MazeCell;
+    /// Gets the index of the given cell into per-cell storage laid out row by row
+    fn cell_index(&self, cell: MazeCell) -> usize {
+        (cell.x + cell.y * self.width) as usize
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.current_y > self.max_y {
-            return None;
+    /// Returns true if the wall at the given index exists and is open
+    /// Maze edges (represented by a None index) are never open
+    fn is_wall_open(&self, wall_index: Option<usize>) -> bool {
+        match wall_index {
+            Some(index) => match self.walls[index] {
+                Wall::Open => true,
+                Wall::Closed => false,
+            },
+            None => false,
         }
+    }
 
-        if self.current_x < self.max_x {
-            // have not hit right-most wall, increment x
-            let x = self.current_x;
-            let y = self.current_y;
-
-            self.current_x += 1;
+    fn get_movement_options_for(&self, cell: MazeCell) -> MovementOptions {
+        let MazeCell { x, y } = cell;
 
-            Some(MazeCell::new(x, y))
+        let north = if self.is_wall_open(self.north_wall_index_for_cell(x, y)) {
+            Some(MazeCell::new(x, y + 1))
         } else {
-            // on right-most wall
-            // wrap x and increment y
-            let x = self.current_x;
-            let y = self.current_y;
+            None
+        };
+        let east = if self.is_wall_open(self.east_wall_index_for_cell(x, y)) {
+            Some(MazeCell::new(x + 1, y))
+        } else {
+            None
+        };
+        let south = if self.is_wall_open(self.south_wall_index_for_cell(x, y)) {
+            Some(MazeCell::new(x, y - 1))
+        } else {
+            None
+        };
+        let west = if self.is_wall_open(self.west_wall_index_for_cell(x, y)) {
+            Some(MazeCell::new(x - 1, y))
+        } else {
+            None
+        };
 
-            self.current_x = 0;
-            self.current_y += 1;
+        MovementOptions::new(north, east, south, west)
+    }
 
-            Some(MazeCell::new(x, y))
+    /// Returns the neighboring cells reachable through an open wall, in north, east,
+    /// south, west order
+    pub fn open_neighbors(&self, cell: MazeCell) -> impl Iterator<Item = MazeCell> + '_ {
+        let MazeCell { x, y } = cell;
+        Direction::ALL
+            .iter()
+            .filter_map(move |direction| match direction {
+                Direction::North if self.is_wall_open(self.north_wall_index_for_cell(x, y)) => {
+                    Some(MazeCell::new(x, y + 1))
+                }
+                Direction::East if self.is_wall_open(self.east_wall_index_for_cell(x, y)) => {
+                    Some(MazeCell::new(x + 1, y))
+                }
+                Direction::South if self.is_wall_open(self.south_wall_index_for_cell(x, y)) => {
+                    Some(MazeCell::new(x, y - 1))
+                }
+                Direction::West if self.is_wall_open(self.west_wall_index_for_cell(x, y)) => {
+                    Some(MazeCell::new(x - 1, y))
+                }
+                _ => None,
+            })
+    }
+
+    /// Returns the neighboring cell in the given direction if the wall between them
+    /// is open. Returns None for closed walls and the maze edge.
+    pub fn try_move(&self, from: MazeCell, direction: Direction) -> Option<MazeCell> {
+        let options = self.get_movement_options_for(from);
+        match direction {
+            Direction::North => options.north,
+            Direction::East => options.east,
+            Direction::South => options.south,
+            Direction::West => options.west,
         }
     }
-}
 
-impl MovementOptions {
-    fn new(
-        north: Option<MazeCell>,
-        east: Option<MazeCell>,
-        south: Option<MazeCell>,
-        west: Option<MazeCell>,
-    ) -> Self {
-        MovementOptions {
-            north,
-            east,
+    /// Returns every cell which can be seen from the given cell by looking straight
+    /// down open passages to the north, east, south, and west. Sight stops at the
+    /// first closed wall in each direction. The starting cell is always included.
+    pub fn visible_cells(&self, from: MazeCell) -> Vec<MazeCell> {
+        let mut visible = vec![from];
+        for &direction in Direction::ALL.iter() {
+            let mut current = from;
+            while let Some(next) = self.try_move(current, direction) {
+                visible.push(next);
+                current = next;
+            }
+        }
+
+        visible
+    }
+
+    /// Returns the endpoints of every closed wall segment, including the maze border.
+    /// Endpoints are grid-corner coordinates, where corner (0, 0) is the bottom left
+    /// corner of cell (0, 0) and corner (width, height) is the top right corner of the maze.
+    pub fn wall_segments(&self) -> Vec<((u32, u32), (u32, u32))> {
+        let mut segments = vec![];
+
+        // internal walls
+        for cell in MazeIterator::new(self) {
+            let MazeCell { x, y } = cell;
+            if let Some(index) = self.north_wall_index_for_cell(x, y) {
+                if let Wall::Closed = self.walls[index] {
+                    segments.push(((x, y + 1), (x + 1, y + 1)));
+                }
+            }
+            if let Some(index) = self.east_wall_index_for_cell(x, y) {
+                if let Wall::Closed = self.walls[index] {
+                    segments.push(((x + 1, y), (x + 1, y + 1)));
+                }
+            }
+        }
+
+        // maze border
+        for x in 0..self.width {
+            segments.push(((x, 0), (x + 1, 0)));
+            segments.push(((x, self.height), (x + 1, self.height)));
+        }
+        for y in 0..self.height {
+            segments.push(((0, y), (0, y + 1)));
+            segments.push(((self.width, y), (self.width, y + 1)));
+        }
+
+        segments
+    }
+
+    /// Returns the shortest path from start to end, including both endpoints
+    /// Returns None if end cannot be reached from start
+    pub fn solve(&self, start: MazeCell, end: MazeCell) -> Option<Vec<MazeCell>> {
+        MazePath::new(self, start).path_to(end)
+    }
+
+    /// Returns the shortest path from start to end like `solve`, but reports why no
+    /// path was found
+    pub fn solve_detailed(
+        &self,
+        start: MazeCell,
+        end: MazeCell,
+    ) -> Result<Vec<MazeCell>, SolveError> {
+        if !self.contains(start) {
+            return Err(SolveError::StartOutOfBounds(start));
+        }
+        if !self.contains(end) {
+            return Err(SolveError::EndOutOfBounds(end));
+        }
+
+        self.solve(start, end).ok_or(SolveError::Unreachable)
+    }
+
+    /// Returns the number of moves on the shortest path from start to end, without
+    /// building the path itself
+    /// Returns None if end cannot be reached from start
+    pub fn solution_length(&self, start: MazeCell, end: MazeCell) -> Option<u32> {
+        MazePath::new(self, start).distance_to(end)
+    }
+
+    /// Returns the average number of wrong turns offered at each cell along the shortest
+    /// path from start to end, counting the open walls of each path cell other than the
+    /// ones the path itself passes through
+    /// Returns 0 if end cannot be reached from start.
+    pub fn solution_branching(&self, start: MazeCell, end: MazeCell) -> f64 {
+        let path = match self.solve(start, end) {
+            Some(path) => path,
+            None => return 0.0,
+        };
+
+        let branches: usize = path
+            .iter()
+            .enumerate()
+            .map(|(i, &cell)| {
+                let used = usize::from(i > 0) + usize::from(i + 1 < path.len());
+                self.open_wall_count(cell) - used
+            })
+            .sum();
+
+        branches as f64 / path.len() as f64
+    }
+
+    /// Returns the fraction of all cells which lie on the shortest path from start to
+    /// end, including both endpoints. Returns 0 if end cannot be reached from start.
+    pub fn solution_fraction(&self, start: MazeCell, end: MazeCell) -> f64 {
+        match self.solution_length(start, end) {
+            Some(length) => f64::from(length + 1) / f64::from(self.width * self.height),
+            None => 0.0,
+        }
+    }
+
+    /// Returns the moves which walk the shortest path from start to end
+    /// Returns None if end cannot be reached from start
+    pub fn solve_directions(&self, start: MazeCell, end: MazeCell) -> Option<Vec<Direction>> {
+        let path = self.solve(start, end)?;
+
+        let directions = path
+            .windows(2)
+            .map(|step| {
+                let (from, to) = (step[0], step[1]);
+                if to.y > from.y {
+                    Direction::North
+                } else if to.x > from.x {
+                    Direction::East
+                } else if to.y < from.y {
+                    Direction::South
+                } else {
+                    Direction::West
+                }
+            })
+            .collect();
+
+        Some(directions)
+    }
+
+    /// Returns how many different shortest paths lead from start to end
+    /// A perfect maze always has exactly one, and an unreachable end has none
+    pub fn solution_count(&self, start: MazeCell, end: MazeCell) -> usize {
+        let path = MazePath::new(self, start);
+        let mut counts = vec![vec![0_usize; self.height as usize]; self.width as usize];
+        counts[start.x as usize][start.y as usize] = 1;
+
+        // every shortest path to a cell passes through a neighbor one step closer to
+        // start, so visiting cells in flood fill order finalizes each count in turn
+        for &cell in &path.visit_order {
+            let count = counts[cell.x as usize][cell.y as usize];
+            for next in self.get_movement_options_for(cell).cells() {
+                if path.distance_to(next) == path.distance_to(cell).map(|distance| distance + 1) {
+                    let next_count = &mut counts[next.x as usize][next.y as usize];
+                    *next_count = next_count.saturating_add(count);
+                }
+            }
+        }
+
+        counts[end.x as usize][end.y as usize]
+    }
+
+    /// Returns the cheapest path from start to end, including both endpoints, where
+    /// moving into a cell costs the weight returned for that cell
+    /// With a constant weight this finds the same path length as `solve`
+    /// Returns None if end cannot be reached from start
+    pub fn solve_weighted(
+        &self,
+        start: MazeCell,
+        end: MazeCell,
+        weight_fn: impl Fn(MazeCell) -> u32,
+    ) -> Option<Vec<MazeCell>> {
+        let mut costs: Vec<Vec<Option<u64>>> =
+            vec![vec![None; self.height as usize]; self.width as usize];
+        let mut parents: Vec<Vec<Option<MazeCell>>> =
+            vec![vec![None; self.height as usize]; self.width as usize];
+
+        costs[start.x as usize][start.y as usize] = Some(0);
+        let mut queue = BinaryHeap::new();
+        queue.push(Reverse((0_u64, start.x, start.y)));
+
+        while let Some(Reverse((cost, x, y))) = queue.pop() {
+            let cell = MazeCell::new(x, y);
+            if cell == end {
+                break;
+            }
+            if costs[x as usize][y as usize].is_some_and(|best| cost > best) {
+                // a cheaper route to this cell was already expanded
+                continue;
+            }
+
+            for next in self.get_movement_options_for(cell).cells() {
+                let next_cost = cost + u64::from(weight_fn(next));
+                let best = &mut costs[next.x as usize][next.y as usize];
+                if best.map_or(true, |best| next_cost < best) {
+                    *best = Some(next_cost);
+                    parents[next.x as usize][next.y as usize] = Some(cell);
+                    queue.push(Reverse((next_cost, next.x, next.y)));
+                }
+            }
+        }
+
+        costs[end.x as usize][end.y as usize]?;
+        let mut path = vec![end];
+        let mut current = end;
+        while current != start {
+            current = parents[current.x as usize][current.y as usize]?;
+            path.push(current);
+        }
+        path.reverse();
+
+        Some(path)
+    }
+
+    /// Returns the cells along the given edge of the maze, in order of increasing x
+    /// for the north and south edges and increasing y for the east and west edges
+    pub fn border_cells(&self, side: Direction) -> Vec<MazeCell> {
+        match side {
+            Direction::North => (0..self.width)
+                .map(|x| MazeCell::new(x, self.height - 1))
+                .collect(),
+            Direction::South => (0..self.width).map(|x| MazeCell::new(x, 0)).collect(),
+            Direction::East => (0..self.height)
+                .map(|y| MazeCell::new(self.width - 1, y))
+                .collect(),
+            Direction::West => (0..self.height).map(|y| MazeCell::new(0, y)).collect(),
+        }
+    }
+
+    /// Returns a start and end pair of cells along the edge of the maze whose shortest
+    /// path distance is as close as possible to target_length. Ties are broken in
+    /// favor of the first pair found in row order.
+    /// If no two edge cells are connected, the first edge cell is returned as both
+    /// the start and end.
+    pub fn suggest_endpoints(&self, target_length: u32) -> (MazeCell, MazeCell) {
+        let edge_cells: Vec<MazeCell> = MazeIterator::new(self)
+            .filter(|cell| {
+                cell.x == 0 || cell.y == 0 || cell.x == self.width - 1 || cell.y == self.height - 1
+            })
+            .collect();
+
+        let mut best: Option<(MazeCell, MazeCell, u32)> = None;
+        for (i, &start) in edge_cells.iter().enumerate() {
+            let path = MazePath::new(self, start);
+            for &end in &edge_cells[i + 1..] {
+                if let Some(distance) = path.distance_to(end) {
+                    let error = distance.abs_diff(target_length);
+                    if best.map_or(true, |(_start, _end, best_error)| error < best_error) {
+                        best = Some((start, end, error));
+                    }
+                }
+            }
+        }
+
+        match best {
+            Some((start, end, _error)) => (start, end),
+            None => (edge_cells[0], edge_cells[0]),
+        }
+    }
+
+    /// Returns the shortest path distance between every pair of cells, where cell (x, y)
+    /// is at index x + y * width along both axes. Unreachable pairs are u32::MAX.
+    pub fn distance_matrix(&self) -> Vec<Vec<u32>> {
+        MazeIterator::new(self)
+            .map(|from| {
+                let path = MazePath::new(self, from);
+                MazeIterator::new(self)
+                    .map(|to| path.distance_to(to).unwrap_or(u32::MAX))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Returns the neighbors reachable through an open wall from each cell, where cell
+    /// (x, y) is node x + y * width and neighbors are listed north, east, south, west
+    pub fn adjacency_list(&self) -> Vec<Vec<usize>> {
+        MazeIterator::new(self)
+            .map(|cell| {
+                self.open_neighbors(cell)
+                    .map(|neighbor| self.cell_index(neighbor))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Returns the greatest distance from the cell to any cell reachable from it
+    pub fn eccentricity(&self, cell: MazeCell) -> u32 {
+        let path = MazePath::new(self, cell);
+        path.visit_order
+            .iter()
+            .filter_map(|&other| path.distance_to(other))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns the reachable cell furthest from exit, which makes the longest solution
+    /// when used as the start. Returns exit itself if nothing else is reachable.
+    pub fn hardest_start_for(&self, exit: MazeCell) -> MazeCell {
+        let path = MazePath::new(self, exit);
+        // a breadth first flood reaches the furthest cell last
+        *path.visit_order.last().unwrap()
+    }
+
+    /// Returns the cells with the smallest eccentricity, in row order starting at (0, 0)
+    /// These are the most central cells when moving through the maze passages
+    pub fn graph_center(&self) -> Vec<MazeCell> {
+        let eccentricities: Vec<(MazeCell, u32)> = MazeIterator::new(self)
+            .map(|cell| (cell, self.eccentricity(cell)))
+            .collect();
+        let min_eccentricity = eccentricities
+            .iter()
+            .map(|&(_cell, eccentricity)| eccentricity)
+            .min()
+            .unwrap_or(0);
+
+        eccentricities
+            .into_iter()
+            .filter(|&(_cell, eccentricity)| eccentricity == min_eccentricity)
+            .map(|(cell, _eccentricity)| cell)
+            .collect()
+    }
+
+    /// Returns every cell reachable from start paired with its distance from start,
+    /// in the order a breadth first flood fill reaches them (nearest cells first)
+    pub fn bfs_order(&self, start: MazeCell) -> impl Iterator<Item = (MazeCell, u32)> {
+        let MazePath {
+            distances,
+            visit_order,
+            ..
+        } = MazePath::new(self, start);
+
+        visit_order.into_iter().map(move |cell| {
+            let distance = distances[cell.x as usize][cell.y as usize];
+            (cell, distance.unwrap())
+        })
+    }
+
+    /// Returns every cell whose shortest path distance from start is exactly d, in row
+    /// order starting at (0, 0)
+    pub fn cells_at_distance(&self, start: MazeCell, d: u32) -> Vec<MazeCell> {
+        let path = MazePath::new(self, start);
+        MazeIterator::new(self)
+            .filter(|&cell| path.distance_to(cell) == Some(d))
+            .collect()
+    }
+
+    /// Returns the parent of each cell in the breadth first tree of shortest paths
+    /// rooted at root, indexed as [x][y]
+    /// The root and any cells which can't reach it have no parent.
+    pub fn spanning_tree_from(&self, root: MazeCell) -> Vec<Vec<Option<MazeCell>>> {
+        MazePath::new(self, root).parents
+    }
+
+    /// Counts, for each cell, how many cells (including itself) route through it on
+    /// their shortest path toward end. Cells with high traffic are chokepoints.
+    /// The result is indexed as [x][y], with cells which can't reach end having zero
+    /// traffic, and is all zero if end can't be reached from start.
+    pub fn cell_traffic(&self, start: MazeCell, end: MazeCell) -> Vec<Vec<u32>> {
+        let path = MazePath::new(self, end);
+        let mut traffic = vec![vec![0; self.height as usize]; self.width as usize];
+        if path.distance_to(start).is_none() {
+            return traffic;
+        }
+
+        // visit the furthest cells first so each cell's count is final before it is
+        // passed to its parent
+        for &cell in path.visit_order.iter().rev() {
+            traffic[cell.x as usize][cell.y as usize] += 1;
+            if let Some(parent) = path.parent_of(cell) {
+                traffic[parent.x as usize][parent.y as usize] +=
+                    traffic[cell.x as usize][cell.y as usize];
+            }
+        }
+
+        traffic
+    }
+
+    /// Returns the width, height, and state of every wall (0 for open, 1 for closed)
+    /// Walls are ordered as documented at the top of this module, with all horizontal
+    /// walls followed by all vertical walls
+    pub fn to_flat(&self) -> (u32, u32, Vec<u8>) {
+        let walls = self
+            .walls
+            .iter()
+            .map(|wall| match wall {
+                Wall::Open => 0,
+                Wall::Closed => 1,
+            })
+            .collect();
+
+        (self.width, self.height, walls)
+    }
+
+    /// Encodes the maze as its dimensions followed by runs of open (`O`) and closed (`C`)
+    /// walls, in the order returned by `to_flat`, such as `3x2:2O1C4O`
+    pub fn to_rle(&self) -> String {
+        let mut encoded = format!("{}x{}:", self.width, self.height);
+        let mut walls = self.walls.iter().peekable();
+        while let Some(&wall) = walls.next() {
+            let mut run = 1;
+            while walls.next_if(|&&next| next == wall).is_some() {
+                run += 1;
+            }
+            let symbol = match wall {
+                Wall::Open => 'O',
+                Wall::Closed => 'C',
+            };
+            encoded.push_str(&format!("{}{}", run, symbol));
+        }
+
+        encoded
+    }
+
+    /// Decodes a maze encoded by `to_rle`
+    pub fn from_rle(s: &str) -> Result<Maze, ParseError> {
+        let (dimensions, runs) = s.split_once(':').ok_or(ParseError::InvalidDimensions)?;
+        let (width, height) = dimensions
+            .split_once('x')
+            .and_then(|(width, height)| Some((width.parse().ok()?, height.parse().ok()?)))
+            .filter(|&(width, height)| width > 0 && height > 0)
+            .ok_or(ParseError::InvalidDimensions)?;
+
+        let mut maze = Maze::new(width, height);
+        let mut walls = Vec::with_capacity(maze.walls.len());
+        let mut run = String::new();
+        let mut run_column = 0;
+        for (column, c) in runs.chars().enumerate() {
+            let column = dimensions.len() + 1 + column;
+            let wall = match c {
+                '0'..='9' => {
+                    if run.is_empty() {
+                        run_column = column;
+                    }
+                    run.push(c);
+                    continue;
+                }
+                'O' => Wall::Open,
+                'C' => Wall::Closed,
+                c => return Err(ParseError::UnexpectedChar(c, 0, column)),
+            };
+            let count = if run.is_empty() {
+                1
+            } else {
+                match run.parse::<usize>() {
+                    Ok(count) if count > 0 => count,
+                    _ => return Err(ParseError::InvalidRun(run_column)),
+                }
+            };
+            // check the length before extending so a huge run can't exhaust memory
+            let total = walls.len().saturating_add(count);
+            if total > maze.walls.len() {
+                return Err(ParseError::WrongWallCount(maze.walls.len(), total));
+            }
+            walls.extend(std::iter::repeat(wall).take(count));
+            run.clear();
+        }
+        if !run.is_empty() {
+            return Err(ParseError::InvalidRun(run_column));
+        }
+
+        if walls.len() != maze.walls.len() {
+            return Err(ParseError::WrongWallCount(maze.walls.len(), walls.len()));
+        }
+        maze.walls = walls;
+
+        Ok(maze)
+    }
+
+    /// Returns every cell which, if blocked, would disconnect cells that are currently
+    /// connected to each other. Cells are returned in row order starting at (0, 0).
+    pub fn articulation_points(&self) -> Vec<MazeCell> {
+        let num_cells = (self.width * self.height) as usize;
+        let mut discovered: Vec<Option<u32>> = vec![None; num_cells];
+        let mut low = vec![0; num_cells];
+        let mut is_articulation_point = vec![false; num_cells];
+        let mut time = 0;
+
+        // iterative depth first search computing the low-link value for each cell
+        for root in MazeIterator::new(self) {
+            if discovered[self.cell_index(root)].is_some() {
+                continue;
+            }
+            discovered[self.cell_index(root)] = Some(time);
+            low[self.cell_index(root)] = time;
+            time += 1;
+
+            let mut root_children = 0;
+            let mut stack = vec![(root, self.get_movement_options_for(root).cells(), 0)];
+            while let Some((cell, neighbors, next_neighbor)) = stack.last_mut() {
+                let cell = *cell;
+                let cell_index = self.cell_index(cell);
+
+                if let Some(&neighbor) = neighbors.get(*next_neighbor) {
+                    *next_neighbor += 1;
+                    let neighbor_index = self.cell_index(neighbor);
+                    match discovered[neighbor_index] {
+                        Some(neighbor_time) => {
+                            low[cell_index] = low[cell_index].min(neighbor_time);
+                        }
+                        None => {
+                            discovered[neighbor_index] = Some(time);
+                            low[neighbor_index] = time;
+                            time += 1;
+                            if cell == root {
+                                root_children += 1;
+                            }
+                            let neighbors = self.get_movement_options_for(neighbor).cells();
+                            stack.push((neighbor, neighbors, 0));
+                        }
+                    }
+                } else {
+                    stack.pop();
+                    if let Some(&(parent, _, _)) = stack.last() {
+                        let parent_index = self.cell_index(parent);
+                        low[parent_index] = low[parent_index].min(low[cell_index]);
+                        if parent != root && Some(low[cell_index]) >= discovered[parent_index] {
+                            is_articulation_point[parent_index] = true;
+                        }
+                    }
+                }
+            }
+
+            // the root is only an articulation point if it joins separate subtrees
+            if root_children > 1 {
+                is_articulation_point[self.cell_index(root)] = true;
+            }
+        }
+
+        MazeIterator::new(self)
+            .filter(|&cell| is_articulation_point[self.cell_index(cell)])
+            .collect()
+    }
+
+    /// Returns the cells which every path from start to end must pass through, in order
+    /// along the shortest path and including start and end themselves
+    /// Blocking any one of them would leave end unreachable. Returns an empty list if
+    /// end already can't be reached from start.
+    pub fn bottleneck_cells(&self, start: MazeCell, end: MazeCell) -> Vec<MazeCell> {
+        let path = match self.solve(start, end) {
+            Some(path) => path,
+            None => return vec![],
+        };
+
+        // every bottleneck must be on the shortest path, so only those cells are tried
+        let reaches_end_without = |blocked: MazeCell| {
+            let mut reached = vec![false; (self.width * self.height) as usize];
+            reached[self.cell_index(start)] = true;
+            let mut frontier = vec![start];
+            while let Some(cell) = frontier.pop() {
+                if cell == end {
+                    return true;
+                }
+                for next in self.get_movement_options_for(cell).cells() {
+                    let index = self.cell_index(next);
+                    if next != blocked && !reached[index] {
+                        reached[index] = true;
+                        frontier.push(next);
+                    }
+                }
+            }
+            false
+        };
+
+        path.iter()
+            .filter(|&&cell| cell == start || cell == end || !reaches_end_without(cell))
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the cell reachable from the given cell which is closest to the center of
+    /// the grid by straight line distance. Ties are broken in favor of the cell which
+    /// comes first in row order starting at (0, 0).
+    pub fn centermost_reachable(&self, from: MazeCell) -> MazeCell {
+        let path = MazePath::new(self, from);
+        let center_x = f64::from(self.width - 1) / 2.0;
+        let center_y = f64::from(self.height - 1) / 2.0;
+        let distance_to_center =
+            |cell: MazeCell| (f64::from(cell.x) - center_x).hypot(f64::from(cell.y) - center_y);
+
+        MazeIterator::new(self)
+            .filter(|&cell| path.distance_to(cell).is_some())
+            .fold(from, |closest, cell| {
+                if distance_to_center(cell) < distance_to_center(closest) {
+                    cell
+                } else {
+                    closest
+                }
+            })
+    }
+
+    /// Returns every cell with exactly one open wall, in row order starting at (0, 0)
+    pub fn dead_ends(&self) -> Vec<MazeCell> {
+        self.cells_with_degree(|degree| degree == 1)
+    }
+
+    /// Returns the two dead ends with the longest shortest path between them, in row
+    /// order. Ties are broken in favor of the first pair found in row order.
+    /// Returns None if fewer than two dead ends are connected to each other.
+    pub fn farthest_deadend_pair(&self) -> Option<(MazeCell, MazeCell)> {
+        let dead_ends = self.dead_ends();
+
+        let mut farthest: Option<(MazeCell, MazeCell, u32)> = None;
+        for (i, &a) in dead_ends.iter().enumerate() {
+            let path = MazePath::new(self, a);
+            for &b in &dead_ends[i + 1..] {
+                if let Some(distance) = path.distance_to(b) {
+                    if farthest.map_or(true, |(_a, _b, farthest_distance)| {
+                        distance > farthest_distance
+                    }) {
+                        farthest = Some((a, b, distance));
+                    }
+                }
+            }
+        }
+
+        farthest.map(|(a, b, _distance)| (a, b))
+    }
+
+    /// Returns the dead end which is furthest from the shortest path between start
+    /// and end, measured along the maze passages
+    /// Returns None if end can't be reached or every dead end is on the path
+    pub fn deepest_offpath_deadend(&self, start: MazeCell, end: MazeCell) -> Option<MazeCell> {
+        let solution = self.solve(start, end)?;
+        let distances = self.distances_from_cells(&solution);
+
+        self.dead_ends()
+            .into_iter()
+            .filter_map(|cell| {
+                distances[cell.x as usize][cell.y as usize]
+                    .filter(|&distance| distance > 0)
+                    .map(|distance| (cell, distance))
+            })
+            // prefer the first of equally deep dead ends
+            .fold(
+                None,
+                |deepest: Option<(MazeCell, u32)>, (cell, distance)| match deepest {
+                    Some((_deepest, deepest_distance)) if deepest_distance >= distance => deepest,
+                    _ => Some((cell, distance)),
+                },
+            )
+            .map(|(cell, _distance)| cell)
+    }
+
+    /// Returns, for each cell, its distance along the passages to the nearest cell on
+    /// the shortest path from start to end, indexed as [x][y]
+    /// Cells on the path are 0. Cells which can't reach the path, and every cell when
+    /// end can't be reached from start, are u32::MAX.
+    pub fn distance_to_solution(&self, start: MazeCell, end: MazeCell) -> Vec<Vec<u32>> {
+        let solution = self.solve(start, end).unwrap_or_default();
+
+        self.distances_from_cells(&solution)
+            .into_iter()
+            .map(|column| {
+                column
+                    .into_iter()
+                    .map(|distance| distance.unwrap_or(u32::MAX))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Returns, for each cell, its distance along the passages to the nearest dead end,
+    /// indexed as [x][y]. Cells with no open walls are their own dead end.
+    /// Cells in a group of loops with no dead end at all are u32::MAX.
+    pub fn corridor_depth(&self) -> Vec<Vec<u32>> {
+        let dead_ends = self.cells_with_degree(|degree| degree <= 1);
+        self.distances_from_cells(&dead_ends)
+            .into_iter()
+            .map(|column| {
+                column
+                    .into_iter()
+                    .map(|distance| distance.unwrap_or(u32::MAX))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Scores how hard it is to corner an agent at cell, where lower scores are more
+    /// dangerous. Rather than the distance to the nearest junction (a cell with three
+    /// or more open walls) itself, the score is the number of cells in the maze less
+    /// that distance, so junctions score highest and cells deep in dead end corridors
+    /// score lowest. Cells with no reachable junction score 0.
+    pub fn trap_score(&self, cell: MazeCell) -> u32 {
+        // searching breadth first reaches the nearest junction first, so only the cells
+        // closer than it are visited
+        let mut reached = vec![vec![false; self.height as usize]; self.width as usize];
+        reached[cell.x as usize][cell.y as usize] = true;
+        let mut frontier = VecDeque::new();
+        frontier.push_back((cell, 0));
+        while let Some((current, distance)) = frontier.pop_front() {
+            if self.open_wall_count(current) >= 3 {
+                return self.width * self.height - distance;
+            }
+            for next in self.get_movement_options_for(current).cells() {
+                let next_reached = &mut reached[next.x as usize][next.y as usize];
+                if !*next_reached {
+                    *next_reached = true;
+                    frontier.push_back((next, distance + 1));
+                }
+            }
+        }
+
+        0
+    }
+
+    /// Floods the maze from every one of the given cells at once, returning the
+    /// distance from each cell to the nearest source, indexed as [x][y]
+    fn distances_from_cells(&self, sources: &[MazeCell]) -> Vec<Vec<Option<u32>>> {
+        let mut distances = vec![vec![None; self.height as usize]; self.width as usize];
+        let mut frontier = VecDeque::new();
+        for &source in sources {
+            distances[source.x as usize][source.y as usize] = Some(0);
+            frontier.push_back((source, 0));
+        }
+
+        while let Some((cell, distance)) = frontier.pop_front() {
+            for next in self.get_movement_options_for(cell).cells() {
+                let next_distance = &mut distances[next.x as usize][next.y as usize];
+                if next_distance.is_none() {
+                    *next_distance = Some(distance + 1);
+                    frontier.push_back((next, distance + 1));
+                }
+            }
+        }
+
+        distances
+    }
+
+    /// Returns every cell with three or more open walls, in row order starting at (0, 0)
+    pub fn junctions(&self) -> Vec<MazeCell> {
+        self.cells_with_degree(|degree| degree >= 3)
+    }
+
+    /// Returns the number of open walls (from 0 to 4) around each cell, indexed as [x][y]
+    /// Dead ends have degree 1, junctions 3 or more, and isolated cells 0
+    pub fn degrees(&self) -> Vec<Vec<u8>> {
+        let mut degrees = vec![vec![0; self.height as usize]; self.width as usize];
+        for index in 0..self.walls.len() {
+            if self.walls[index] == Wall::Open {
+                let (a, b) = self.cells_for_wall(index);
+                degrees[a.x as usize][a.y as usize] += 1;
+                degrees[b.x as usize][b.y as usize] += 1;
+            }
+        }
+
+        degrees
+    }
+
+    /// Counts the cells with each degree, so element 0 is the number of isolated cells,
+    /// element 1 the number of dead ends, and so on up to four open walls
+    pub fn degree_histogram(&self) -> [usize; 5] {
+        let mut histogram = [0; 5];
+        for degree in self.degrees().into_iter().flatten() {
+            histogram[degree as usize] += 1;
+        }
+
+        histogram
+    }
+
+    /// Returns the number of three way (T) junctions and four way crossroads
+    pub fn junction_types(&self) -> (usize, usize) {
+        let histogram = self.degree_histogram();
+
+        (histogram[3], histogram[4])
+    }
+
+    /// Returns the Shannon entropy, in bits, of the distribution of cell degrees
+    /// Mazes mixing dead ends, corridors, and junctions score higher than mazes made
+    /// mostly of a single kind of cell, and a maze where every cell has the same degree
+    /// scores zero.
+    pub fn entropy(&self) -> f64 {
+        let cells = (self.width * self.height) as f64;
+        self.degree_histogram()
+            .iter()
+            .filter(|&&count| count > 0)
+            .map(|&count| {
+                let p = count as f64 / cells;
+                -p * p.log2()
+            })
+            .sum()
+    }
+
+    /// Returns every cell whose degree satisfies the predicate, in row order starting
+    /// at (0, 0)
+    fn cells_with_degree(&self, predicate: impl Fn(u8) -> bool) -> Vec<MazeCell> {
+        let degrees = self.degrees();
+        MazeIterator::new(self)
+            .filter(|cell| predicate(degrees[cell.x as usize][cell.y as usize]))
+            .collect()
+    }
+
+    /// Repeatedly closes off dead ends other than start and end, until only the
+    /// passages between start and end (plus any loops) remain open
+    /// On a perfect maze this leaves exactly the solution path open
+    pub fn fill_dead_ends(&mut self, start: MazeCell, end: MazeCell) {
+        let mut dead_ends = self.dead_ends();
+
+        while let Some(dead_end) = dead_ends.pop() {
+            if dead_end == start || dead_end == end {
+                continue;
+            }
+            // a dead end has exactly one neighbor, which may become a dead end once
+            // the passage to it is closed
+            if let Some(&neighbor) = self.get_movement_options_for(dead_end).cells().first() {
+                let index = self.wall_index_between(dead_end, neighbor).unwrap();
+                self.walls[index] = Wall::Closed;
+                if self.open_wall_count(neighbor) == 1 {
+                    dead_ends.push(neighbor);
+                }
+            }
+        }
+    }
+
+    /// Returns the number of open passages allowing vertical (north/south) movement
+    /// and the number allowing horizontal (east/west) movement
+    pub fn passage_bias(&self) -> (usize, usize) {
+        // horizontal walls are stored before all of the vertical walls
+        let num_horizontal_segments = ((self.height - 1) * self.width) as usize;
+        let (horizontal_walls, vertical_walls) = self.walls.split_at(num_horizontal_segments);
+        let count_open = |walls: &[Wall]| walls.iter().filter(|&&wall| wall == Wall::Open).count();
+
+        (count_open(horizontal_walls), count_open(vertical_walls))
+    }
+
+    /// Returns the number of independent loops in the maze (the cyclomatic number of
+    /// its passages), which is zero for a perfect maze
+    pub fn loop_count(&self) -> usize {
+        let mut components = DisjointSet::new((self.width * self.height) as usize);
+        // every open wall which doesn't join two separate components closes a loop
+        (0..self.walls.len())
+            .filter(|&index| self.walls[index] == Wall::Open)
+            .filter(|&index| {
+                let (a, b) = self.cells_for_wall(index);
+                !components.union(self.cell_index(a), self.cell_index(b))
+            })
+            .count()
+    }
+
+    /// Counts, for each cell, how many of the maze's independent loops pass through it,
+    /// indexed as [x][y]
+    /// The loops counted are those closed by each open wall outside of a breadth first
+    /// spanning forest, so there are `loop_count` of them and cells on no loop are zero.
+    pub fn loop_density(&self) -> Vec<Vec<u32>> {
+        let (width, height) = (self.width as usize, self.height as usize);
+        let mut parents: Vec<Vec<Option<MazeCell>>> = vec![vec![None; height]; width];
+        let mut depths: Vec<Vec<Option<u32>>> = vec![vec![None; height]; width];
+        for root in MazeIterator::new(self) {
+            if depths[root.x as usize][root.y as usize].is_some() {
+                continue;
+            }
+            depths[root.x as usize][root.y as usize] = Some(0);
+            let mut queue = VecDeque::new();
+            queue.push_back(root);
+            while let Some(cell) = queue.pop_front() {
+                let depth = depths[cell.x as usize][cell.y as usize].unwrap();
+                for neighbor in self.open_neighbors(cell) {
+                    let (x, y) = (neighbor.x as usize, neighbor.y as usize);
+                    if depths[x][y].is_none() {
+                        depths[x][y] = Some(depth + 1);
+                        parents[x][y] = Some(cell);
+                        queue.push_back(neighbor);
+                    }
+                }
+            }
+        }
+
+        let mut density = vec![vec![0; height]; width];
+        for index in 0..self.walls.len() {
+            if self.walls[index] == Wall::Closed {
+                continue;
+            }
+            let (mut a, mut b) = self.cells_for_wall(index);
+            let parent = |cell: MazeCell| parents[cell.x as usize][cell.y as usize];
+            if parent(a) == Some(b) || parent(b) == Some(a) {
+                continue;
+            }
+
+            // the loop runs up the tree from both sides of the wall to where they meet
+            let depth = |cell: MazeCell| depths[cell.x as usize][cell.y as usize];
+            while a != b {
+                let deeper = if depth(a) >= depth(b) { &mut a } else { &mut b };
+                density[deeper.x as usize][deeper.y as usize] += 1;
+                *deeper = parent(*deeper).unwrap();
+            }
+            density[a.x as usize][a.y as usize] += 1;
+        }
+
+        density
+    }
+
+    /// Returns the fewest closed walls which would need to be opened to connect every
+    /// cell, which is one less than the number of separate groups of connected cells
+    pub fn min_walls_to_connect(&self) -> usize {
+        let mut components = DisjointSet::new((self.width * self.height) as usize);
+        let joins = (0..self.walls.len())
+            .filter(|&index| self.walls[index] == Wall::Open)
+            .filter(|&index| {
+                let (a, b) = self.cells_for_wall(index);
+                components.union(self.cell_index(a), self.cell_index(b))
+            })
+            .count();
+
+        (self.width * self.height) as usize - joins - 1
+    }
+
+    /// Counts the open passages in each quadrant of the grid, ordered south west, south
+    /// east, north west, north east
+    /// A passage belongs to the quadrant of its southern or western cell, and when the
+    /// width or height is odd the middle column or row belongs to the east or north.
+    pub fn quadrant_open_counts(&self) -> [usize; 4] {
+        let mut counts = [0; 4];
+        for index in 0..self.walls.len() {
+            if self.walls[index] == Wall::Open {
+                let (cell, _other) = self.cells_for_wall(index);
+                let east = cell.x >= self.width / 2;
+                let north = cell.y >= self.height / 2;
+                counts[usize::from(east) + 2 * usize::from(north)] += 1;
+            }
+        }
+
+        counts
+    }
+
+    /// Returns the fraction of cells with exactly two open walls whose passages run
+    /// straight through (north to south or east to west) rather than turning a corner
+    /// Long straight runs score close to 1, twisty mazes close to 0. Returns 0 if no
+    /// cell has exactly two open walls.
+    pub fn river_factor(&self) -> f64 {
+        let (mut corridors, mut straight) = (0, 0);
+        for cell in MazeIterator::new(self) {
+            let options = self.get_movement_options_for(cell);
+            if options.cells().len() != 2 {
+                continue;
+            }
+            corridors += 1;
+            let vertical = options.north.is_some() && options.south.is_some();
+            let horizontal = options.east.is_some() && options.west.is_some();
+            if vertical || horizontal {
+                straight += 1;
+            }
+        }
+
+        if corridors == 0 {
+            0.0
+        } else {
+            f64::from(straight) / f64::from(corridors)
+        }
+    }
+
+    /// Estimates how many steps a random walk starting at (0, 0) takes to visit every
+    /// cell reachable from there, averaged over samples walks
+    /// Each step moves through an open wall chosen uniformly at random.
+    /// Returns None if samples is 0
+    pub fn estimated_cover_time(&self, samples: usize, seed: u64) -> Option<f64> {
+        self.estimated_cover_time_with_rng(samples, &mut RandRng(StdRng::seed_from_u64(seed)))
+    }
+
+    pub fn estimated_cover_time_with_rng(
+        &self,
+        samples: usize,
+        rng: &mut impl MazeRng,
+    ) -> Option<f64> {
+        if samples == 0 {
+            return None;
+        }
+
+        let start = MazeCell::new(0, 0);
+        let reachable = MazePath::new(self, start).visit_order.len();
+
+        let mut total_steps = 0u64;
+        for _sample in 0..samples {
+            let mut visited = vec![false; (self.width * self.height) as usize];
+            visited[self.cell_index(start)] = true;
+            let mut unvisited = reachable - 1;
+            let mut current = start;
+            while unvisited > 0 {
+                let options = self.get_movement_options_for(current).cells();
+                current = options[rng.next_index(options.len())];
+                total_steps += 1;
+                let index = self.cell_index(current);
+                if !visited[index] {
+                    visited[index] = true;
+                    unvisited -= 1;
+                }
+            }
+        }
+
+        Some(total_steps as f64 / samples as f64)
+    }
+
+    /// Returns the number of open walls around the cell, between 0 and 4
+    fn open_wall_count(&self, cell: MazeCell) -> usize {
+        self.get_movement_options_for(cell).cells().len()
+    }
+
+    /// Renders the maze with box drawing characters directly into the given writer,
+    /// which avoids building the whole rendering as a String first
+    pub fn write_to(&self, w: &mut impl fmt::Write) -> fmt::Result {
+        self.write_with_labels(w, RenderStyle::Sharp, |_cell| None)
+    }
+
+    /// Returns the cell drawn at the given character column and line of the `Display`
+    /// output, counting from zero at the top left
+    /// Returns None for wall and corner characters and for positions outside the drawing.
+    pub fn cell_at_char(&self, col: usize, row: usize) -> Option<MazeCell> {
+        // each cell is drawn three characters wide and one line tall, between walls
+        if col % 4 == 0 || row % 2 == 0 {
+            return None;
+        }
+        let x = (col / 4) as u32;
+        let rows_from_top = (row / 2) as u32;
+        if x >= self.width || rows_from_top >= self.height {
+            return None;
+        }
+
+        Some(MazeCell::new(x, self.height - 1 - rows_from_top))
+    }
+
+    /// Returns the cell under the given point of an SVG from `to_wall_path_svg` drawn
+    /// with the same cell_size, where px and py are measured right and down from the
+    /// top left corner of the image
+    /// Returns None for points outside the maze.
+    pub fn cell_from_pixel(&self, px: f64, py: f64, cell_size: f64) -> Option<MazeCell> {
+        let column = (px / cell_size).floor();
+        let row = (py / cell_size).floor();
+        // this also rejects the infinite or NaN values from a zero cell_size
+        let in_bounds = |value: f64, limit: u32| value >= 0.0 && value < f64::from(limit);
+        if !in_bounds(column, self.width) || !in_bounds(row, self.height) {
+            return None;
+        }
+
+        // rows are counted down from the top of the image but up from the bottom of the maze
+        Some(MazeCell::new(column as u32, self.height - 1 - row as u32))
+    }
+
+    /// Renders the maze with box drawing characters, returning each line separately
+    /// without line endings
+    pub fn render_lines(&self) -> Vec<String> {
+        self.to_string()
+            .split(LINE_ENDING)
+            .map(String::from)
+            .collect()
+    }
+
+    /// Prints the maze followed by a line ending to stdout, streaming the rendering
+    /// through a buffer rather than building it as a String first
+    pub fn print(&self) -> io::Result<()> {
+        let stdout = io::stdout();
+        self.print_to(stdout.lock())
+    }
+
+    /// Writes the maze followed by a line ending to the given io writer, as `print` does
+    /// for stdout
+    pub fn print_to(&self, w: impl io::Write) -> io::Result<()> {
+        let mut w = BufWriter::new(w);
+        write!(w, "{}{}", self, LINE_ENDING)?;
+        w.flush()
+    }
+
+    /// Renders the maze in the common ASCII style, with `+` corners, `---` walls
+    /// between rows, and `|` walls between columns
+    pub fn to_ascii(&self) -> String {
+        let mut lines = vec![];
+        for y in (0..self.height).rev() {
+            let mut north_walls = String::from("+");
+            let mut cells = String::from("|");
+            for x in 0..self.width {
+                let options = self.get_movement_options_for(MazeCell::new(x, y));
+                north_walls.push_str(if options.north.is_some() {
+                    "   +"
+                } else {
+                    "---+"
+                });
+                cells.push_str(if options.east.is_some() {
+                    "    "
+                } else {
+                    "   |"
+                });
+            }
+            lines.push(north_walls);
+            lines.push(cells);
+        }
+        lines.push(format!("+{}", "---+".repeat(self.width as usize)));
+
+        lines.join(LINE_ENDING)
+    }
+
+    /// Returns Rust source for an expression which rebuilds this maze from its ASCII
+    /// drawing, for capturing generated mazes as test fixtures
+    pub fn to_rust_literal(&self) -> String {
+        let ascii = self.to_ascii();
+        let lines: Vec<&str> = ascii.split(LINE_ENDING).collect();
+        let mut literal = format!("Maze::from_ascii(concat!({}", LINE_ENDING);
+        for (i, line) in lines.iter().enumerate() {
+            let line_ending = if i + 1 < lines.len() { "\\n" } else { "" };
+            literal.push_str(&format!("    \"{}{}\",{}", line, line_ending, LINE_ENDING));
+        }
+        literal.push_str("))");
+        literal.push_str(LINE_ENDING);
+        literal.push_str(".unwrap()");
+
+        literal
+    }
+
+    /// Parses a maze drawn in the common ASCII style, as produced by `to_ascii`
+    /// Each cell is three characters wide and one line tall. Walls are drawn with `-`
+    /// and `|` and passages with spaces, while corners and the maze border may be
+    /// drawn with any character.
+    pub fn from_ascii(s: &str) -> Result<Maze, ParseError> {
+        let lines: Vec<Vec<char>> = s.lines().map(|line| line.chars().collect()).collect();
+        let columns = lines.first().map_or(0, |line| line.len());
+        if lines.len() < 3 || columns < 5 {
+            return Err(ParseError::TooSmall);
+        }
+        if let Some(line) = lines.iter().position(|line| line.len() != columns) {
+            return Err(ParseError::RaggedLine(line));
+        }
+        if lines.len() % 2 != 1 || columns % 4 != 1 {
+            return Err(ParseError::UnevenGrid);
+        }
+
+        let width = (columns / 4) as u32;
+        let height = (lines.len() / 2) as u32;
+        let mut maze = Maze::new(width, height);
+        let is_open = |wall: char, line: usize, column: usize| match lines[line][column] {
+            ' ' => Ok(true),
+            c if c == wall => Ok(false),
+            c => Err(ParseError::UnexpectedChar(c, line, column)),
+        };
+
+        let cells: Vec<MazeCell> = MazeIterator::new(&maze).collect();
+        for cell in cells {
+            // the first line is the top edge of the maze
+            let line = 2 * (height - 1 - cell.y) as usize + 1;
+            let column = 4 * cell.x as usize;
+
+            if cell.y + 1 < height {
+                let mut open = true;
+                for offset in 1..4 {
+                    open &= is_open('-', line - 1, column + offset)?;
+                }
+                if open {
+                    maze.open_north_wall(cell).unwrap();
+                }
+            }
+            if cell.x + 1 < width && is_open('|', line, column + 4)? {
+                maze.open_east_wall(cell).unwrap();
+            }
+        }
+
+        Ok(maze)
+    }
+
+    /// Renders the maze with an `S` in the start cell and an `E` in the end cell
+    pub fn render_with_endpoints(&self, start: MazeCell, end: MazeCell) -> String {
+        self.render_with_endpoint_markers(start, end, &EndpointMarkers::default())
+    }
+
+    /// Renders the maze with the given markers in the start and end cells
+    pub fn render_with_endpoint_markers(
+        &self,
+        start: MazeCell,
+        end: MazeCell,
+        markers: &EndpointMarkers,
+    ) -> String {
+        let mut rendered = String::new();
+        let _ = self.write_with_labels(&mut rendered, RenderStyle::Sharp, |cell| {
+            match (cell == start, cell == end) {
+                (true, true) => Some(markers.both),
+                (true, false) => Some(markers.start),
+                (false, true) => Some(markers.end),
+                (false, false) => None,
+            }
+        });
+
+        rendered
+    }
+
+    /// Renders the maze, placing the label returned for each cell in the middle of that
+    /// cell and leaving cells blank where the label is None
+    pub fn render_with_labels(&self, labels: impl Fn(MazeCell) -> Option<char>) -> String {
+        let mut rendered = String::new();
+        let _ = self.write_with_labels(&mut rendered, RenderStyle::Sharp, labels);
+
+        rendered
+    }
+
+    /// Renders the maze using the given style of box drawing characters
+    pub fn render_with_style(&self, style: RenderStyle) -> String {
+        let mut rendered = String::new();
+        let _ = self.write_with_labels(&mut rendered, style, |_cell| None);
+
+        rendered
+    }
+
+    /// Renders one frame of an animated search, using ANSI terminal colors to shade
+    /// visited cells blue, frontier cells yellow, and the current cell red
+    /// A cell in more than one of these takes the color listed last.
+    pub fn render_search_frame(
+        &self,
+        visited: &[MazeCell],
+        frontier: &[MazeCell],
+        current: MazeCell,
+    ) -> String {
+        // cells outside the maze are ignored
+        let grid_of = |cells: &[MazeCell]| {
+            let mut grid = vec![vec![false; self.height as usize]; self.width as usize];
+            for &cell in cells.iter().filter(|&&cell| self.contains(cell)) {
+                grid[cell.x as usize][cell.y as usize] = true;
+            }
+            grid
+        };
+        let is_visited = grid_of(visited);
+        let is_frontier = grid_of(frontier);
+
+        let mut rendered = String::new();
+        let _ = self.write_with_cell_contents(&mut rendered, RenderStyle::Sharp, |cell| {
+            let color = if cell == current {
+                "41"
+            } else if is_frontier[cell.x as usize][cell.y as usize] {
+                "43"
+            } else if is_visited[cell.x as usize][cell.y as usize] {
+                "44"
+            } else {
+                return "   ".to_string();
+            };
+            format!("\x1b[{}m   \x1b[0m", color)
+        });
+
+        rendered
+    }
+
+    /// Renders the maze with box drawing characters, placing the label returned for
+    /// each cell in the middle of that cell
+    fn write_with_labels(
+        &self,
+        w: &mut impl fmt::Write,
+        style: RenderStyle,
+        labels: impl Fn(MazeCell) -> Option<char>,
+    ) -> fmt::Result {
+        self.write_with_cell_contents(w, style, |cell| match labels(cell) {
+            Some(label) => format!(" {} ", label),
+            None => "   ".to_string(),
+        })
+    }
+
+    /// Renders the maze with box drawing characters, filling the inside of each cell
+    /// with the returned text, which should be three columns wide
+    fn write_with_cell_contents(
+        &self,
+        w: &mut impl fmt::Write,
+        style: RenderStyle,
+        contents: impl Fn(MazeCell) -> String,
+    ) -> fmt::Result {
+        write_grid(
+            w,
+            (self.width, self.height),
+            |x, y| self.is_vertical_line_closed(x, y),
+            |x, y| self.is_horizontal_line_closed(x, y),
+            style,
+            contents,
+        )
+    }
+
+    /// Returns true if the wall along vertical line x beside row y is closed, where the
+    /// lines at x = 0 and x = width are the maze edges
+    fn is_vertical_line_closed(&self, x: u32, y: u32) -> bool {
+        x == 0 || x == self.width || !self.is_wall_open(self.east_wall_index_for_cell(x - 1, y))
+    }
+
+    /// Returns true if the wall along horizontal line y beside column x is closed, where
+    /// the lines at y = 0 and y = height are the maze edges
+    fn is_horizontal_line_closed(&self, x: u32, y: u32) -> bool {
+        y == 0 || y == self.height || !self.is_wall_open(self.north_wall_index_for_cell(x, y - 1))
+    }
+
+    /// Returns a GeoJSON FeatureCollection with a LineString feature for each open
+    /// passage, running from the center of one cell to the center of the other
+    /// Coordinates increase to the east and north from the bottom left corner of the
+    /// maze, so the center of cell (x, y) is at ((x + 0.5) * cell_size, (y + 0.5) * cell_size)
+    pub fn to_geojson(&self, cell_size: f64) -> String {
+        let center = |cell: MazeCell| {
+            (
+                (f64::from(cell.x) + 0.5) * cell_size,
+                (f64::from(cell.y) + 0.5) * cell_size,
+            )
+        };
+
+        let features: Vec<String> = (0..self.walls.len())
+            .filter(|&index| self.walls[index] == Wall::Open)
+            .map(|index| {
+                let (a, b) = self.cells_for_wall(index);
+                let ((ax, ay), (bx, by)) = (center(a), center(b));
+                format!(
+                    r#"{{"type":"Feature","geometry":{{"type":"LineString","coordinates":[[{},{}],[{},{}]]}},"properties":{{}}}}"#,
+                    ax, ay, bx, by
+                )
+            })
+            .collect();
+
+        format!(
+            r#"{{"type":"FeatureCollection","features":[{}{}{}]}}"#,
+            LINE_ENDING,
+            features.join(&format!(",{}", LINE_ENDING)),
+            LINE_ENDING
+        )
+    }
+
+    /// Returns an SVG document drawing only the closed walls, for laser cutting or pen
+    /// plotting. Collinear walls which touch are merged into a single path so the pen
+    /// is lifted as rarely as possible.
+    /// The top left corner of the maze is at (0, 0) and each cell is cell_size wide.
+    pub fn to_wall_path_svg(&self, cell_size: f64) -> String {
+        let (width, height) = (self.width as usize, self.height as usize);
+        // horizontal[y][x] is the wall from corner (x, y) to (x + 1, y), and
+        // vertical[x][y] is the wall from corner (x, y) to (x, y + 1)
+        let mut horizontal = vec![vec![false; width]; height + 1];
+        let mut vertical = vec![vec![false; height]; width + 1];
+        for ((x1, y1), (x2, _y2)) in self.wall_segments() {
+            if x1 == x2 {
+                vertical[x1 as usize][y1 as usize] = true;
+            } else {
+                horizontal[y1 as usize][x1 as usize] = true;
+            }
+        }
+
+        let point = |x: usize, y: usize| {
+            format!(
+                "{} {}",
+                x as f64 * cell_size,
+                (height - y) as f64 * cell_size
+            )
+        };
+        let mut lines = vec![];
+        for (y, row) in horizontal.iter().enumerate() {
+            for (start, end) in closed_runs(row) {
+                lines.push((point(start, y), point(end, y)));
+            }
+        }
+        for (x, column) in vertical.iter().enumerate() {
+            for (start, end) in closed_runs(column) {
+                lines.push((point(x, start), point(x, end)));
+            }
+        }
+
+        let mut svg = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}">{}"#,
+            width as f64 * cell_size,
+            height as f64 * cell_size,
+            LINE_ENDING
+        );
+        for (from, to) in lines {
+            svg.push_str(&format!(
+                r#"<path d="M{} L{}" stroke="black" fill="none"/>{}"#,
+                from, to, LINE_ENDING
+            ));
+        }
+        svg.push_str("</svg>");
+
+        svg
+    }
+
+    /// Draws the maze as an image, centering each wall on the edge between two cells
+    /// The image has a margin of half a wall on every side so the border walls are
+    /// drawn at full thickness. Pixels partly covered by a wall are blended with the
+    /// background, which keeps fractional thicknesses smooth.
+    pub fn to_image_styled(&self, style: ImageStyle) -> Image {
+        let cell_size = f64::from(style.cell_size);
+        let padding = style.wall_thickness.ceil() as u32;
+        let mut image = Image::new(
+            self.width * style.cell_size + padding,
+            self.height * style.cell_size + padding,
+            style.background_color,
+        );
+
+        // how much of each pixel is covered by a wall, taking the largest single wall
+        // where walls overlap at corners
+        let mut coverage = vec![vec![0.0f64; image.height() as usize]; image.width() as usize];
+        for ((x1, y1), (x2, y2)) in self.wall_segments() {
+            // walls are centered on their edge after shifting everything by the margin,
+            // so each covers wall_thickness pixels starting at its edge. y counts down
+            // from the top of the image.
+            let left = f64::from(x1.min(x2)) * cell_size;
+            let right = f64::from(x1.max(x2)) * cell_size + style.wall_thickness;
+            let top = f64::from(self.height - y1.max(y2)) * cell_size;
+            let bottom = f64::from(self.height - y1.min(y2)) * cell_size + style.wall_thickness;
+
+            let columns = left.floor() as u32..(right.ceil() as u32).min(image.width());
+            for px in columns {
+                let rows = top.floor() as u32..(bottom.ceil() as u32).min(image.height());
+                for py in rows {
+                    let overlap_x = right.min(f64::from(px + 1)) - left.max(f64::from(px));
+                    let overlap_y = bottom.min(f64::from(py + 1)) - top.max(f64::from(py));
+                    let covered = &mut coverage[px as usize][py as usize];
+                    *covered = covered.max(overlap_x.max(0.0) * overlap_y.max(0.0));
+                }
+            }
+        }
+
+        for (px, column) in coverage.iter().enumerate() {
+            for (py, &covered) in column.iter().enumerate() {
+                if covered > 0.0 {
+                    image.blend(px as u32, py as u32, style.wall_color, covered);
+                }
+            }
+        }
+
+        image
+    }
+
+    /// Renders the maze as a grid of blocks, with `#` for walls and spaces for passages
+    /// Each cell is drawn as a square of corridor_cells characters and each wall is
+    /// drawn wall_cells characters thick
+    pub fn render_scaled(&self, corridor_cells: u32, wall_cells: u32) -> String {
+        render_wall_grid(&self.scaled_wall_grid(corridor_cells, wall_cells), "#", " ")
+    }
+
+    /// Renders the block grid (see `to_block_grid`) with 🟦 for walls and ⬜ for passages
+    pub fn to_emoji(&self) -> String {
+        render_wall_grid(&self.to_block_grid(), "🟦", "⬜")
+    }
+
+    /// Renders the block grid (see `to_block_grid`) with walls shaded by orientation
+    /// for a raised look: ▓ for walls running east to west, ▒ for walls running north
+    /// to south, and ░ for the posts where walls meet
+    pub fn render_shaded(&self) -> String {
+        let grid = self.to_block_grid();
+        let rows = grid[0].len();
+
+        (0..rows)
+            .rev()
+            .map(|y| {
+                (0..grid.len())
+                    .map(|x| match (grid[x][y], x % 2, y % 2) {
+                        (false, _x, _y) => ' ',
+                        (true, 0, 0) => '░',
+                        (true, _x, 0) => '▓',
+                        (true, _x, _y) => '▒',
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>()
+            .join(LINE_ENDING)
+    }
+
+    /// Returns the maze as a (2 * width + 1) x (2 * height + 1) grid which is true
+    /// wherever there is a wall. The grid is indexed as [x][y] with (0, 0) at the
+    /// bottom left, so the center of cell (x, y) is at [2 * x + 1][2 * y + 1] and is
+    /// always open. Positions between two cell centers hold the wall between them,
+    /// and positions between four cell centers are always walls.
+    pub fn to_block_grid(&self) -> Vec<Vec<bool>> {
+        self.scaled_wall_grid(1, 1)
+    }
+
+    /// Builds a maze from a block grid laid out as in `to_block_grid`, opening each wall
+    /// whose position between two cell centers is false
+    /// The positions along the outer edge and between four cell centers are ignored, as
+    /// they are always walls. Errors if the grid is ragged or not an odd size, or if any
+    /// cell center is marked as a wall.
+    pub fn from_block_grid(grid: &[Vec<bool>]) -> Result<Maze, MazeError> {
+        let columns = grid.len();
+        let rows = grid.first().map_or(0, |column| column.len());
+        let is_valid = |len: usize| len >= 3 && len % 2 == 1;
+        if !is_valid(columns) || !is_valid(rows) {
+            return Err(MazeError::InvalidBlockGrid(columns, rows));
+        }
+        if let Some(column) = grid.iter().find(|column| column.len() != rows) {
+            return Err(MazeError::InvalidBlockGrid(columns, column.len()));
+        }
+
+        let mut maze = Maze::new((columns / 2) as u32, (rows / 2) as u32);
+        let cells: Vec<MazeCell> = MazeIterator::new(&maze).collect();
+        for cell in cells {
+            let (x, y) = (2 * cell.x as usize + 1, 2 * cell.y as usize + 1);
+            if grid[x][y] {
+                return Err(MazeError::CellCenterBlocked(cell));
+            }
+            if cell.x + 1 < maze.width && !grid[x + 1][y] {
+                maze.open_east_wall(cell).unwrap();
+            }
+            if cell.y + 1 < maze.height && !grid[x][y + 1] {
+                maze.open_north_wall(cell).unwrap();
+            }
+        }
+
+        Ok(maze)
+    }
+
+    /// Builds a grid, indexed as [x][y] with (0, 0) at the bottom left, which is true
+    /// wherever a wall is drawn when cells are corridor_cells wide and walls are
+    /// wall_cells thick
+    fn scaled_wall_grid(&self, corridor_cells: u32, wall_cells: u32) -> Vec<Vec<bool>> {
+        // each position along an axis is either on the line between cells (numbered
+        // from zero at the bottom left edge) or inside a cell
+        enum Position {
+            Line(u32),
+            Cell(u32),
+        }
+        let positions = |num_cells: u32| {
+            let mut positions = vec![];
+            for i in 0..=num_cells {
+                positions.extend((0..wall_cells).map(|_| Position::Line(i)));
+                if i < num_cells {
+                    positions.extend((0..corridor_cells).map(|_| Position::Cell(i)));
+                }
+            }
+            positions
+        };
+        let (x_positions, y_positions) = (positions(self.width), positions(self.height));
+
+        x_positions
+            .iter()
+            .map(|x_position| {
+                y_positions
+                    .iter()
+                    .map(|y_position| match (x_position, y_position) {
+                        (Position::Cell(_x), Position::Cell(_y)) => false,
+                        (Position::Line(_x), Position::Line(_y)) => true,
+                        (&Position::Line(x), &Position::Cell(y)) => {
+                            x == 0
+                                || x == self.width
+                                || !self.is_wall_open(self.east_wall_index_for_cell(x - 1, y))
+                        }
+                        (&Position::Cell(x), &Position::Line(y)) => {
+                            y == 0
+                                || y == self.height
+                                || !self.is_wall_open(self.north_wall_index_for_cell(x, y - 1))
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Renders only the w x h window of cells whose bottom left cell is (x0, y0)
+    /// Walls leading out of the window are drawn as the window border
+    /// Returns an error if the window is empty or extends beyond the maze
+    pub fn render_viewport(&self, x0: u32, y0: u32, w: u32, h: u32) -> Result<String, MazeError> {
+        let rect = Rect::new(x0, y0, w, h);
+        self.check_rect(rect)?;
+
+        Ok(self.sub_maze(rect).to_string())
+    }
+
+    /// Renders the shortest path from start to end as an answer key, drawing only the
+    /// walls around cells on the path and leaving the rest of the maze blank
+    /// The drawing is the same size as the full rendering, and is entirely blank if end
+    /// cannot be reached from start.
+    pub fn render_solution_only(&self, start: MazeCell, end: MazeCell) -> String {
+        let path: HashSet<MazeCell> = self
+            .solve(start, end)
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+        let on_path = |x: Option<u32>, y: Option<u32>| match (x, y) {
+            (Some(x), Some(y)) => path.contains(&MazeCell::new(x, y)),
+            _ => false,
+        };
+
+        // only the closed walls which border a cell on the path are drawn
+        let mut rendered = String::new();
+        let _ = write_grid(
+            &mut rendered,
+            (self.width, self.height),
+            |x, y| {
+                self.is_vertical_line_closed(x, y)
+                    && (on_path(x.checked_sub(1), Some(y)) || on_path(Some(x), Some(y)))
+            },
+            |x, y| {
+                self.is_horizontal_line_closed(x, y)
+                    && (on_path(Some(x), y.checked_sub(1)) || on_path(Some(x), Some(y)))
+            },
+            RenderStyle::Sharp,
+            |_cell| "   ",
+        );
+
+        rendered
+    }
+
+    /// Generates mazes with seeds counting up from the given seed until one satisfies
+    /// the predicate. Returns None if none of the first max_tries mazes satisfy it.
+    pub fn generate_until(
+        width: u32,
+        height: u32,
+        algorithm: Algorithm,
+        seed: u64,
+        predicate: impl Fn(&Maze) -> bool,
+        max_tries: usize,
+    ) -> Option<Maze> {
+        (0..max_tries as u64)
+            .map(|attempt| algorithm.generate_seeded(width, height, seed.wrapping_add(attempt)))
+            .find(|maze| predicate(maze))
+    }
+
+    /// Generates count mazes, using seeds counting up from base_seed
+    pub fn generate_batch(
+        width: u32,
+        height: u32,
+        algorithm: Algorithm,
+        count: usize,
+        base_seed: u64,
+    ) -> Vec<Maze> {
+        let mut mazes = Vec::with_capacity(count);
+        for i in 0..count as u64 {
+            mazes.push(algorithm.generate_seeded(width, height, base_seed.wrapping_add(i)));
+        }
+
+        mazes
+    }
+
+    /// Returns, for each wall index, the fraction of samples mazes in which that wall
+    /// is open, generating the mazes with seeds counting up from base_seed
+    /// Walls are ordered as in `to_flat`. Every fraction is 0 when samples is 0.
+    pub fn open_probability_grid(
+        samples: usize,
+        width: u32,
+        height: u32,
+        algorithm: Algorithm,
+        base_seed: u64,
+    ) -> Vec<f64> {
+        let mut open_counts = vec![0; Maze::new(width, height).walls.len()];
+        for maze in Maze::generate_batch(width, height, algorithm, samples, base_seed) {
+            for (count, &wall) in open_counts.iter_mut().zip(&maze.walls) {
+                if wall == Wall::Open {
+                    *count += 1;
+                }
+            }
+        }
+
+        if samples == 0 {
+            return vec![0.0; open_counts.len()];
+        }
+
+        open_counts
+            .into_iter()
+            .map(|count| f64::from(count) / samples as f64)
+            .collect()
+    }
+
+    /// Generates mazes with seeds counting up from the given seed until one has a
+    /// diameter (the longest shortest path between any two cells) of at least min_path
+    /// Gives up after 100 tries, returning the maze with the longest diameter seen.
+    pub fn generate_min_difficulty(
+        width: u32,
+        height: u32,
+        algorithm: Algorithm,
+        min_path: u32,
+        seed: u64,
+    ) -> Maze {
+        let mut best: Option<(Maze, u32)> = None;
+        for attempt in 0..100 {
+            let maze = algorithm.generate_seeded(width, height, seed.wrapping_add(attempt));
+            let diameter = maze.tree_diameter();
+            if diameter >= min_path {
+                return maze;
+            }
+            if best
+                .as_ref()
+                .map_or(true, |(_best, best_diameter)| diameter > *best_diameter)
+            {
+                best = Some((maze, diameter));
+            }
+        }
+
+        best.unwrap().0
+    }
+
+    /// Returns the longest shortest path between any two cells of a perfect maze
+    /// The furthest cell from any cell is always one end of the longest path in a tree.
+    fn tree_diameter(&self) -> u32 {
+        let one_end = self.hardest_start_for(MazeCell::new(0, 0));
+        self.eccentricity(one_end)
+    }
+
+    /// Generates a perfect maze with the recursive backtracker, then braids it by
+    /// opening a wall out of randomly chosen dead ends until no more than target (a
+    /// fraction between 0 and 1) of its cells are dead ends
+    /// Each opened wall removes one or two dead ends, so the final ratio is at most
+    /// two cells' worth below target. Targets outside of 0 to 1 are clamped to that
+    /// range, and a NaN target is treated as 0.
+    pub fn generate_with_deadend_ratio(width: u32, height: u32, target: f64, seed: u64) -> Maze {
+        let mut maze = Maze::recursive_backtracker_seeded(width, height, seed);
+        let mut rng = StdRng::seed_from_u64(seed);
+        let num_cells = f64::from(width * height);
+        let target = if target.is_nan() {
+            0.0
+        } else {
+            target.clamp(0.0, 1.0)
+        };
+
+        loop {
+            let dead_ends = maze.dead_ends();
+            if dead_ends.is_empty() || dead_ends.len() as f64 / num_cells <= target {
+                break;
+            }
+
+            let dead_end = dead_ends[rng.gen_range(0, dead_ends.len())];
+            let closed_neighbors: Vec<MazeCell> = maze
+                .neighbors_of(dead_end)
+                .into_iter()
+                .filter(|&neighbor| !maze.is_wall_open(maze.wall_index_between(dead_end, neighbor)))
+                .collect();
+            if closed_neighbors.is_empty() {
+                // only possible in a single row or column maze, which can't be braided
+                break;
+            }
+            let neighbor = closed_neighbors[rng.gen_range(0, closed_neighbors.len())];
+            maze.open_wall_between(dead_end, neighbor);
+        }
+
+        maze
+    }
+
+    /// Opens every wall between cells inside rect, turning it into a single chamber
+    /// Walls along the edge of rect are left as they are
+    pub fn open_room(&mut self, rect: Rect) -> Result<(), MazeError> {
+        self.check_rect(rect)?;
+
+        for x in rect.x..rect.x + rect.width {
+            for y in rect.y..rect.y + rect.height {
+                let cell = MazeCell::new(x, y);
+                if y + 1 < rect.y + rect.height {
+                    let _ = self.open_north_wall(cell);
+                }
+                if x + 1 < rect.x + rect.width {
+                    let _ = self.open_east_wall(cell);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns every chamber of at least min_size cells, where a chamber is a rectangle
+    /// at least two cells wide and two cells tall with every wall inside it open, and
+    /// which can't grow in any direction without including a closed wall
+    /// Chambers may overlap, and are returned in row order of their bottom left cell.
+    pub fn chambers(&self, min_size: u32) -> Vec<Rect> {
+        let (width, height) = (self.width as usize, self.height as usize);
+        // closed_counts[x][y] counts the closed east and north walls of the cells below
+        // and to the left of (x, y), so the closed walls in any rectangle can be summed
+        // from its four corners
+        let mut closed_counts = vec![vec![(0, 0); height + 1]; width + 1];
+        for x in 0..width {
+            for y in 0..height {
+                let (cell_x, cell_y) = (x as u32, y as u32);
+                let closed = |index: Option<usize>| match index {
+                    Some(index) if self.walls[index] == Wall::Closed => 1,
+                    _ => 0,
+                };
+                let east = closed(self.east_wall_index_for_cell(cell_x, cell_y));
+                let north = closed(self.north_wall_index_for_cell(cell_x, cell_y));
+                let (left, below, diagonal) = (
+                    closed_counts[x][y + 1],
+                    closed_counts[x + 1][y],
+                    closed_counts[x][y],
+                );
+                closed_counts[x + 1][y + 1] = (
+                    left.0 + below.0 - diagonal.0 + east,
+                    left.1 + below.1 - diagonal.1 + north,
+                );
+            }
+        }
+        let count_closed = |x0: u32, y0: u32, x1: u32, y1: u32, wall: fn((i32, i32)) -> i32| {
+            let (x0, y0, x1, y1) = (x0 as usize, y0 as usize, x1 as usize, y1 as usize);
+            wall(closed_counts[x1][y1]) - wall(closed_counts[x0][y1]) - wall(closed_counts[x1][y0])
+                + wall(closed_counts[x0][y0])
+        };
+        // the east walls of the right column and north walls of the top row are the
+        // edge of the rectangle rather than inside it
+        let is_open = |rect: Rect| {
+            let (x1, y1) = (rect.x + rect.width, rect.y + rect.height);
+            count_closed(rect.x, rect.y, x1 - 1, y1, |counts| counts.0) == 0
+                && count_closed(rect.x, rect.y, x1, y1 - 1, |counts| counts.1) == 0
+        };
+        let can_grow = |rect: Rect| {
+            let Rect {
+                x,
+                y,
+                width,
+                height,
+            } = rect;
+            (x > 0 && is_open(Rect::new(x - 1, y, width + 1, height)))
+                || (x + width < self.width && is_open(Rect::new(x, y, width + 1, height)))
+                || (y > 0 && is_open(Rect::new(x, y - 1, width, height + 1)))
+                || (y + height < self.height && is_open(Rect::new(x, y, width, height + 1)))
+        };
+
+        let mut chambers = vec![];
+        for cell in MazeIterator::new(self) {
+            for width in 2..=self.width - cell.x {
+                for height in 2..=self.height - cell.y {
+                    let rect = Rect::new(cell.x, cell.y, width, height);
+                    if !is_open(rect) {
+                        // any taller rectangle contains this one's closed wall too
+                        break;
+                    }
+                    if width * height >= min_size && !can_grow(rect) {
+                        chambers.push(rect);
+                    }
+                }
+            }
+        }
+
+        chambers
+    }
+
+    /// Returns a new maze holding only the cells inside rect, with passages which
+    /// crossed the edge of rect closed off
+    pub fn crop(&self, rect: Rect) -> Result<Maze, MazeError> {
+        self.check_rect(rect)?;
+
+        Ok(self.sub_maze(rect))
+    }
+
+    /// Regenerates the cells inside rect with the given algorithm, leaving every wall
+    /// outside of rect unchanged. Passages crossing the edge of rect are kept, so
+    /// cells which could reach the region before can still reach it afterwards. If no
+    /// passage crosses the edge, one is opened to connect the region to the rest of
+    /// the maze.
+    pub fn regenerate_region(
+        &mut self,
+        rect: Rect,
+        algorithm: Algorithm,
+        seed: u64,
+    ) -> Result<(), MazeError> {
+        self.check_rect(rect)?;
+
+        let region = algorithm.generate_seeded(rect.width, rect.height, seed);
+        self.paste(&region, rect.x, rect.y);
+
+        let border_walls: Vec<(MazeCell, MazeCell)> = MazeIterator::new(self)
+            .filter(|&cell| rect.contains(cell))
+            .flat_map(|cell| {
+                self.neighbors_of(cell)
+                    .into_iter()
+                    .filter(|&neighbor| !rect.contains(neighbor))
+                    .map(move |neighbor| (cell, neighbor))
+            })
+            .collect();
+        let connected = border_walls
+            .iter()
+            .any(|&(cell, neighbor)| self.is_wall_open(self.wall_index_between(cell, neighbor)));
+        if !connected && !border_walls.is_empty() {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let (cell, neighbor) = border_walls[rng.gen_range(0, border_walls.len())];
+            self.open_wall_between(cell, neighbor);
+        }
+
+        Ok(())
+    }
+
+    /// Rearranges a perfect maze by repeatedly closing a random open wall and opening
+    /// a random closed wall which reconnects the two halves, so the maze stays perfect
+    /// Makes at most 10 attempts per requested swap, since some mazes (such as a single
+    /// corridor) have no alternative walls to open
+    pub fn perturb(&mut self, swaps: usize, seed: u64) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut completed_swaps = 0;
+
+        for _attempt in 0..swaps * 10 {
+            if completed_swaps == swaps {
+                break;
+            }
+
+            let open_walls: Vec<usize> = (0..self.walls.len())
+                .filter(|&index| self.walls[index] == Wall::Open)
+                .collect();
+            if open_walls.is_empty() {
+                return;
+            }
+            let closed_index = open_walls[rng.gen_range(0, open_walls.len())];
+            self.walls[closed_index] = Wall::Closed;
+
+            // closing a wall in a perfect maze splits it in two, find every other
+            // wall which would join the two halves back together
+            let (side, _other_side) = self.cells_for_wall(closed_index);
+            let path = MazePath::new(self, side);
+            let candidates: Vec<usize> = (0..self.walls.len())
+                .filter(|&index| index != closed_index && self.walls[index] == Wall::Closed)
+                .filter(|&index| {
+                    let (a, b) = self.cells_for_wall(index);
+                    path.distance_to(a).is_some() != path.distance_to(b).is_some()
+                })
+                .collect();
+
+            if candidates.is_empty() {
+                self.walls[closed_index] = Wall::Open;
+                continue;
+            }
+            let opened_index = candidates[rng.gen_range(0, candidates.len())];
+            self.walls[opened_index] = Wall::Open;
+            completed_swaps += 1;
+        }
+    }
+
+    /// Gets the two cells separated by the wall at the given index into the wall array
+    /// For horizontal walls the southern cell is returned first, and for vertical walls
+    /// the western cell is returned first
+    fn cells_for_wall(&self, index: usize) -> (MazeCell, MazeCell) {
+        let index = index as u32;
+        let num_horizontal_segments = (self.height - 1) * self.width;
+
+        if index < num_horizontal_segments {
+            let cell = MazeCell::new(index % self.width, index / self.width);
+            (cell, MazeCell::new(cell.x, cell.y + 1))
+        } else {
+            let index = index - num_horizontal_segments;
+            let cell = MazeCell::new(index / self.height, index % self.height);
+            (cell, MazeCell::new(cell.x + 1, cell.y))
+        }
+    }
+
+    /// Returns a copy of this maze rotated or reflected by the given symmetry
+    pub fn transform(&self, symmetry: Symmetry) -> Maze {
+        let mut transformed = if symmetry.swaps_dimensions() {
+            Maze::new(self.height, self.width)
+        } else {
+            Maze::new(self.width, self.height)
+        };
+
+        for index in 0..self.walls.len() {
+            if self.walls[index] == Wall::Open {
+                let (a, b) = self.cells_for_wall(index);
+                transformed.open_wall_between(
+                    symmetry.apply(a, self.width, self.height),
+                    symmetry.apply(b, self.width, self.height),
+                );
+            }
+        }
+
+        transformed
+    }
+
+    /// Builds a unicursal maze, a single winding path with no choices, on a grid twice
+    /// the width and height of this one
+    /// Each cell becomes a 2x2 block, and the path runs around the passages of this maze
+    /// like a hand kept on one wall. For a perfect maze the path visits every cell of
+    /// the new maze, starting at (0, 0) and ending at (1, 0).
+    pub fn to_unicursal(&self) -> Maze {
+        let mut unicursal = Maze::new(self.width * 2, self.height * 2);
+
+        for cell in MazeIterator::new(self) {
+            let options = self.get_movement_options_for(cell);
+            let sub_cell = |dx: u32, dy: u32| MazeCell::new(cell.x * 2 + dx, cell.y * 2 + dy);
+            let mut edges = vec![];
+
+            // where a wall blocks one side of the cell the path follows the wall,
+            // and where a passage leaves the cell the path runs down both sides of it
+            match options.north {
+                Some(_north) => {
+                    edges.push((sub_cell(0, 1), sub_cell(0, 2)));
+                    edges.push((sub_cell(1, 1), sub_cell(1, 2)));
+                }
+                None => edges.push((sub_cell(0, 1), sub_cell(1, 1))),
+            }
+            match options.east {
+                Some(_east) => {
+                    edges.push((sub_cell(1, 0), sub_cell(2, 0)));
+                    edges.push((sub_cell(1, 1), sub_cell(2, 1)));
+                }
+                None => edges.push((sub_cell(1, 0), sub_cell(1, 1))),
+            }
+            // passages south and west are carved by the neighboring cell, and the
+            // south wall of (0, 0) is left closed to break the loop into a path
+            if options.south.is_none() && cell != MazeCell::new(0, 0) {
+                edges.push((sub_cell(0, 0), sub_cell(1, 0)));
+            }
+            if options.west.is_none() {
+                edges.push((sub_cell(0, 0), sub_cell(0, 1)));
+            }
+
+            for (a, b) in edges {
+                unicursal.open_wall_between(a, b);
+            }
+        }
+
+        unicursal
+    }
+
+    /// Returns true if other is identical to this maze after any of the rotations or
+    /// reflections in `Symmetry::ALL`
+    pub fn is_symmetry_equivalent(&self, other: &Maze) -> bool {
+        Symmetry::ALL
+            .iter()
+            .any(|&symmetry| self.transform(symmetry) == *other)
+    }
+
+    /// Returns the rotations and reflections which map this maze onto itself
+    /// `Symmetry::Identity` is left out, since it holds for every maze.
+    pub fn symmetries(&self) -> Vec<Symmetry> {
+        Symmetry::ALL
+            .iter()
+            .filter(|&&symmetry| symmetry != Symmetry::Identity)
+            .filter(|&&symmetry| self.transform(symmetry) == *self)
+            .cloned()
+            .collect()
+    }
+
+    /// Returns a maze where each wall is open if it is open in either this maze or
+    /// other, which usually adds loops when combining two perfect mazes
+    /// Errors if the mazes are not the same size.
+    pub fn union(&self, other: &Maze) -> Result<Maze, MazeError> {
+        if (self.width, self.height) != (other.width, other.height) {
+            return Err(MazeError::SizeMismatch(
+                (self.width, self.height),
+                (other.width, other.height),
+            ));
+        }
+
+        let mut maze = self.clone();
+        for (wall, &other_wall) in maze.walls.iter_mut().zip(&other.walls) {
+            if other_wall == Wall::Open {
+                *wall = Wall::Open;
+            }
+        }
+
+        Ok(maze)
+    }
+
+    /// Copies the walls of other into this maze, with other's bottom left cell placed
+    /// at (x0, y0). Walls along the edges of the pasted region are left untouched.
+    fn paste(&mut self, other: &Maze, x0: u32, y0: u32) {
+        debug_assert!(x0 + other.width <= self.width && y0 + other.height <= self.height);
+
+        for cell in MazeIterator::new(other) {
+            let (x, y) = (cell.x + x0, cell.y + y0);
+            if let Some(index) = other.north_wall_index_for_cell(cell.x, cell.y) {
+                let self_index = self.north_wall_index_for_cell(x, y).unwrap();
+                self.walls[self_index] = other.walls[index];
+            }
+            if let Some(index) = other.east_wall_index_for_cell(cell.x, cell.y) {
+                let self_index = self.east_wall_index_for_cell(x, y).unwrap();
+                self.walls[self_index] = other.walls[index];
+            }
+        }
+    }
+
+    /// Returns an error if the rect is empty or is not entirely within the maze
+    fn check_rect(&self, rect: Rect) -> Result<(), MazeError> {
+        if rect.width == 0
+            || rect.height == 0
+            || rect.width > self.width.saturating_sub(rect.x)
+            || rect.height > self.height.saturating_sub(rect.y)
+        {
+            return Err(MazeError::RectOutOfBounds(rect));
+        }
+
+        Ok(())
+    }
+
+    /// Copies the cells inside rect into a new maze
+    /// rect must already have passed `check_rect`
+    fn sub_maze(&self, rect: Rect) -> Maze {
+        debug_assert!(self.check_rect(rect).is_ok());
+
+        let mut sub_maze = Maze::new(rect.width, rect.height);
+        for cell in MazeIterator::new(&sub_maze) {
+            let (x, y) = (cell.x + rect.x, cell.y + rect.y);
+            if let Some(index) = sub_maze.north_wall_index_for_cell(cell.x, cell.y) {
+                sub_maze.walls[index] = self.walls[self.north_wall_index_for_cell(x, y).unwrap()];
+            }
+            if let Some(index) = sub_maze.east_wall_index_for_cell(cell.x, cell.y) {
+                sub_maze.walls[index] = self.walls[self.east_wall_index_for_cell(x, y).unwrap()];
+            }
+        }
+
+        sub_maze
+    }
+}
+
+impl fmt::Display for Maze {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        self.write_to(f)
+    }
+}
+
+/// Reads the MAZE_SEED environment variable, ignoring it if it isn't a valid u64
+fn seed_from_env() -> Option<u64> {
+    std::env::var("MAZE_SEED").ok()?.parse().ok()
+}
+
+/// Renders a grid indexed as [x][y] with (0, 0) at the bottom left, drawing wall for
+/// each true position and passage for each false position
+fn render_wall_grid(grid: &[Vec<bool>], wall: &str, passage: &str) -> String {
+    let rows = grid.first().map_or(0, |column| column.len());
+
+    (0..rows)
+        .rev()
+        .map(|y| {
+            grid.iter()
+                .map(|column| if column[y] { wall } else { passage })
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>()
+        .join(LINE_ENDING)
+}
+
+/// Returns the start and end offsets of each run of consecutive true values, where
+/// the end is one past the last value in the run
+/// Returns whether pos is less than start + len, which may not fit in a u32
+fn before_end(pos: u32, start: u32, len: u32) -> bool {
+    start.checked_add(len).map_or(true, |end| pos < end)
+}
+
+fn closed_runs(segments: &[bool]) -> Vec<(usize, usize)> {
+    let mut runs = vec![];
+    let mut run_start = None;
+    for (i, &closed) in segments.iter().enumerate() {
+        match (closed, run_start) {
+            (true, None) => run_start = Some(i),
+            (false, Some(start)) => {
+                runs.push((start, i));
+                run_start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(start) = run_start {
+        runs.push((start, segments.len()));
+    }
+
+    runs
+}
+
+/// Returns the box drawing character for the corner at (x, y) of a width x height grid
+/// of cells, joining whichever of the four wall segments meeting there are drawn
+/// See `write_grid` for how vertical and horizontal describe the drawn walls.
+fn get_corner(
+    (width, height): (u32, u32),
+    x: u32,
+    y: u32,
+    vertical: &impl Fn(u32, u32) -> bool,
+    horizontal: &impl Fn(u32, u32) -> bool,
+) -> &'static str {
+    use Wall::*;
+    let wall = |drawn: bool| if drawn { Closed } else { Open };
+    // the segments running up, right, down, and left from the corner
+    let walls = (
+        wall(y < height && vertical(x, y)),
+        wall(x < width && horizontal(x, y)),
+        wall(y > 0 && vertical(x, y - 1)),
+        wall(x > 0 && horizontal(x - 1, y)),
+    );
+    match walls {
+        (Open, Open, Open, Open) => " ",
+        (Open, Open, Open, Closed) => "╴",
+        (Open, Open, Closed, Open) => "╷",
+        (Open, Open, Closed, Closed) => "┐",
+        (Open, Closed, Open, Open) => "╶",
+        (Open, Closed, Open, Closed) => "─",
+        (Open, Closed, Closed, Open) => "┌",
+        (Open, Closed, Closed, Closed) => "┬",
+        (Closed, Open, Open, Open) => "╵",
+        (Closed, Open, Open, Closed) => "┘",
+        (Closed, Open, Closed, Open) => "│",
+        (Closed, Open, Closed, Closed) => "┤",
+        (Closed, Closed, Open, Open) => "└",
+        (Closed, Closed, Open, Closed) => "┴",
+        (Closed, Closed, Closed, Open) => "├",
+        (Closed, Closed, Closed, Closed) => "┼",
+    }
+}
+
+/// Draws a width x height grid of cells with box drawing characters, filling the inside
+/// of each cell with the returned text, which should be three columns wide
+/// vertical(x, y) is true if the wall along vertical line x beside row y is drawn, and
+/// horizontal(x, y) is true if the wall along horizontal line y beside column x is
+/// drawn, with lines counted from zero at the left and bottom edges of the grid.
+fn write_grid<S: AsRef<str>>(
+    w: &mut impl fmt::Write,
+    (width, height): (u32, u32),
+    vertical: impl Fn(u32, u32) -> bool,
+    horizontal: impl Fn(u32, u32) -> bool,
+    style: RenderStyle,
+    contents: impl Fn(MazeCell) -> S,
+) -> fmt::Result {
+    let horizontal_wall_segment = "───";
+    let vertical_wall_segment = "│";
+    let corner = |x: u32, y: u32| match (
+        style,
+        get_corner((width, height), x, y, &vertical, &horizontal),
+    ) {
+        (RenderStyle::Rounded, "┌") => "╭",
+        (RenderStyle::Rounded, "┐") => "╮",
+        (RenderStyle::Rounded, "└") => "╰",
+        (RenderStyle::Rounded, "┘") => "╯",
+        (_style, corner) => corner,
+    };
+    let horizontal_segment = |x: u32, y: u32| {
+        if horizontal(x, y) {
+            horizontal_wall_segment
+        } else {
+            "   "
+        }
+    };
+    let vertical_segment = |x: u32, y: u32| {
+        if vertical(x, y) {
+            vertical_wall_segment
+        } else {
+            " "
+        }
+    };
+
+    // the top edge
+    w.write_str(corner(0, height))?;
+    for x in 0..width {
+        w.write_str(horizontal_segment(x, height))?;
+        w.write_str(corner(x + 1, height))?;
+    }
+
+    for y in (0..height).rev() {
+        w.write_str(LINE_ENDING)?;
+
+        // each cell's contents followed by its east wall
+        w.write_str(vertical_segment(0, y))?;
+        for x in 0..width {
+            w.write_str(contents(MazeCell::new(x, y)).as_ref())?;
+            w.write_str(vertical_segment(x + 1, y))?;
+        }
+
+        // then each cell's south wall
+        w.write_str(LINE_ENDING)?;
+        w.write_str(corner(0, y))?;
+        for x in 0..width {
+            w.write_str(horizontal_segment(x, y))?;
+            w.write_str(corner(x + 1, y))?;
+        }
+    }
+
+    Ok(())
+}
+
+impl fmt::Display for MazeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            MazeError::CellOutOfBounds(cell) => {
+                write!(f, "cell ({}, {}) is outside of the maze", cell.x, cell.y)
+            }
+            MazeError::NotAdjacent(a, b) => write!(
+                f,
+                "cells ({}, {}) and ({}, {}) are not adjacent",
+                a.x, a.y, b.x, b.y
+            ),
+            MazeError::RectOutOfBounds(rect) => write!(
+                f,
+                "{}x{} rect at ({}, {}) is not within the maze",
+                rect.width, rect.height, rect.x, rect.y
+            ),
+            MazeError::RectsOverlap(a, b) => write!(
+                f,
+                "{}x{} rect at ({}, {}) overlaps {}x{} rect at ({}, {})",
+                a.width, a.height, a.x, a.y, b.width, b.height, b.x, b.y
+            ),
+            MazeError::CellNotCovered(cell) => {
+                write!(f, "cell ({}, {}) is not covered", cell.x, cell.y)
+            }
+            MazeError::CellRepeated(cell) => {
+                write!(f, "cell ({}, {}) appears more than once", cell.x, cell.y)
+            }
+            MazeError::ClosesLoop(a, b) => write!(
+                f,
+                "passage between ({}, {}) and ({}, {}) closes a loop",
+                a.x, a.y, b.x, b.y
+            ),
+            MazeError::EmptyDimensions(width, height) => {
+                write!(f, "{}x{} maze has no cells", width, height)
+            }
+            MazeError::WallCountMismatch(expected, actual) => {
+                write!(f, "expected {} walls but found {}", expected, actual)
+            }
+            MazeError::InvalidBlockGrid(columns, rows) => write!(
+                f,
+                "{}x{} block grid is not an odd size of at least 3x3",
+                columns, rows
+            ),
+            MazeError::CellCenterBlocked(cell) => {
+                write!(f, "center of cell ({}, {}) is a wall", cell.x, cell.y)
+            }
+            MazeError::SizeMismatch(a, b) => write!(
+                f,
+                "{}x{} maze does not match {}x{} maze",
+                a.0, a.1, b.0, b.1
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MazeError {}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            SolveError::StartOutOfBounds(cell) => {
+                write!(f, "start ({}, {}) is outside of the maze", cell.x, cell.y)
+            }
+            SolveError::EndOutOfBounds(cell) => {
+                write!(f, "end ({}, {}) is outside of the maze", cell.x, cell.y)
+            }
+            SolveError::Unreachable => write!(f, "end can't be reached from start"),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            ParseError::TooSmall => write!(f, "text is too small to hold a maze"),
+            ParseError::RaggedLine(line) => {
+                write!(f, "line {} has a different length to the first line", line)
+            }
+            ParseError::UnevenGrid => write!(f, "text does not divide evenly into cells"),
+            ParseError::UnexpectedChar(c, line, column) => write!(
+                f,
+                "unexpected character '{}' at line {} column {}",
+                c, line, column
+            ),
+            ParseError::InvalidDimensions => write!(f, "maze dimensions are invalid"),
+            ParseError::WrongWallCount(expected, actual) => {
+                write!(f, "expected {} walls but found {}", expected, actual)
+            }
+            ParseError::InvalidRun(column) => write!(f, "invalid run at column {}", column),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+}
+
+impl Symmetry {
+    pub const ALL: [Symmetry; 8] = [
+        Symmetry::Identity,
+        Symmetry::Rotate90,
+        Symmetry::Rotate180,
+        Symmetry::Rotate270,
+        Symmetry::MirrorHorizontal,
+        Symmetry::MirrorVertical,
+        Symmetry::MirrorDiagonal,
+        Symmetry::MirrorAntiDiagonal,
+    ];
+
+    /// Returns true if this transform swaps the width and height of the grid
+    fn swaps_dimensions(self) -> bool {
+        match self {
+            Symmetry::Rotate90
+            | Symmetry::Rotate270
+            | Symmetry::MirrorDiagonal
+            | Symmetry::MirrorAntiDiagonal => true,
+            Symmetry::Identity
+            | Symmetry::Rotate180
+            | Symmetry::MirrorHorizontal
+            | Symmetry::MirrorVertical => false,
+        }
+    }
+
+    /// Maps a cell of a width x height grid to its position in the transformed grid
+    fn apply(self, cell: MazeCell, width: u32, height: u32) -> MazeCell {
+        let MazeCell { x, y } = cell;
+        let (max_x, max_y) = (width - 1, height - 1);
+        match self {
+            Symmetry::Identity => MazeCell::new(x, y),
+            Symmetry::Rotate90 => MazeCell::new(max_y - y, x),
+            Symmetry::Rotate180 => MazeCell::new(max_x - x, max_y - y),
+            Symmetry::Rotate270 => MazeCell::new(y, max_x - x),
+            Symmetry::MirrorHorizontal => MazeCell::new(max_x - x, y),
+            Symmetry::MirrorVertical => MazeCell::new(x, max_y - y),
+            Symmetry::MirrorDiagonal => MazeCell::new(y, x),
+            Symmetry::MirrorAntiDiagonal => MazeCell::new(max_y - y, max_x - x),
+        }
+    }
+}
+
+impl Rect {
+    pub fn new(x: u32, y: u32, width: u32, height: u32) -> Self {
+        Rect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    pub fn contains(&self, cell: MazeCell) -> bool {
+        cell.x >= self.x
+            && before_end(cell.x, self.x, self.width)
+            && cell.y >= self.y
+            && before_end(cell.y, self.y, self.height)
+    }
+
+    pub fn overlaps(&self, other: &Rect) -> bool {
+        before_end(self.x, other.x, other.width)
+            && before_end(other.x, self.x, self.width)
+            && before_end(self.y, other.y, other.height)
+            && before_end(other.y, self.y, self.height)
+    }
+}
+
+impl Algorithm {
+    /// Generates a new maze using this algorithm
+    pub fn generate(self, width: u32, height: u32) -> Maze {
+        match self {
+            Algorithm::BinaryTree => Maze::binary_tree(width, height),
+            Algorithm::Sidewinder => Maze::sidewinder(width, height),
+            Algorithm::RecursiveBacktracker => Maze::recursive_backtracker(width, height),
+        }
+    }
+
+    /// Generates a new maze using this algorithm, always producing the same maze for
+    /// the same seed
+    pub fn generate_seeded(self, width: u32, height: u32, seed: u64) -> Maze {
+        match self {
+            Algorithm::BinaryTree => Maze::binary_tree_seeded(width, height, seed),
+            Algorithm::Sidewinder => Maze::sidewinder_seeded(width, height, seed),
+            Algorithm::RecursiveBacktracker => {
+                Maze::recursive_backtracker_seeded(width, height, seed)
+            }
+        }
+    }
+}
+
+impl Default for EndpointMarkers {
+    fn default() -> Self {
+        EndpointMarkers {
+            start: 'S',
+            end: 'E',
+            both: '*',
+        }
+    }
+}
+
+impl DisjointSet {
+    fn new(size: usize) -> Self {
+        DisjointSet {
+            parents: (0..size).collect(),
+        }
+    }
+
+    fn find(&mut self, item: usize) -> usize {
+        let mut root = item;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+        // compress the path so later lookups are faster
+        let mut current = item;
+        while self.parents[current] != root {
+            let next = self.parents[current];
+            self.parents[current] = root;
+            current = next;
+        }
+
+        root
+    }
+
+    /// Joins the sets containing a and b
+    /// Returns false if they were already in the same set
+    fn union(&mut self, a: usize, b: usize) -> bool {
+        let (root_a, root_b) = (self.find(a), self.find(b));
+        if root_a == root_b {
+            return false;
+        }
+        self.parents[root_a] = root_b;
+
+        true
+    }
+}
+
+impl MazeIterator {
+    fn new(maze: &Maze) -> Self {
+        MazeIterator {
+            current_x: 0,
+            current_y: 0,
+            max_x: maze.width - 1,
+            max_y: maze.height - 1,
+        }
+    }
+}
+
+impl Iterator for MazeIterator {
+    type Item = MazeCell;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.current_y > self.max_y {
+            return None;
+        }
+
+        if self.current_x < self.max_x {
+            // have not hit right-most wall, increment x
+            let x = self.current_x;
+            let y = self.current_y;
+
+            self.current_x += 1;
+
+            Some(MazeCell::new(x, y))
+        } else {
+            // on right-most wall
+            // wrap x and increment y
+            let x = self.current_x;
+            let y = self.current_y;
+
+            self.current_x = 0;
+            self.current_y += 1;
+
+            Some(MazeCell::new(x, y))
+        }
+    }
+}
+
+impl<R: rand::Rng> MazeRng for RandRng<R> {
+    fn next_bool(&mut self) -> bool {
+        self.0.gen_bool(0.5)
+    }
+
+    fn next_index(&mut self, n: usize) -> usize {
+        self.0.gen_range(0, n)
+    }
+}
+
+impl MazeRng for SeededSidewinderRng {
+    fn next_bool(&mut self) -> bool {
+        self.0.gen_bool(0.5)
+    }
+
+    fn next_index(&mut self, n: usize) -> usize {
+        self.0.gen::<usize>() % n
+    }
+}
+
+impl<R: MazeRng + ?Sized> MazeRng for &mut R {
+    fn next_bool(&mut self) -> bool {
+        (**self).next_bool()
+    }
+
+    fn next_index(&mut self, n: usize) -> usize {
+        (**self).next_index(n)
+    }
+}
+
+impl RecursiveBacktrackerGen {
+    /// Panics if height or width are < 1
+    pub fn new(width: u32, height: u32, seed: u64) -> Self {
+        // the start cell is drawn as u32s, as it was before MazeRng existed, so existing
+        // seeds keep their mazes
+        let mut rng = StdRng::seed_from_u64(seed);
+        let start = MazeCell::new(rng.gen_range(0, width), rng.gen_range(0, height));
+        Self::resume(Maze::new(width, height), &[start], RandRng(rng))
+    }
+}
+
+impl<R: MazeRng> RecursiveBacktrackerGen<R> {
+    /// Panics if height or width are < 1
+    pub fn with_rng(width: u32, height: u32, mut rng: R) -> Self {
+        let maze = Maze::new(width, height);
+        let start = MazeCell::new(
+            rng.next_index(width as usize) as u32,
+            rng.next_index(height as usize) as u32,
+        );
+        let mut visited = vec![false; (width * height) as usize];
+        visited[maze.cell_index(start)] = true;
+
+        RecursiveBacktrackerGen {
+            maze,
+            rng,
+            visited,
+            stack: vec![start],
+        }
+    }
+
+    /// Continues carving a partly built maze, growing out from the already carved cells
+    /// into every other cell without opening any more walls between carved cells
+    fn resume(maze: Maze, carved: &[MazeCell], rng: R) -> Self {
+        let mut visited = vec![false; (maze.width * maze.height) as usize];
+        for &cell in carved {
+            visited[maze.cell_index(cell)] = true;
+        }
+
+        RecursiveBacktrackerGen {
+            maze,
+            rng,
+            visited,
+            stack: carved.to_vec(),
+        }
+    }
+
+    /// Carves a single passage and returns the cell it carved into
+    /// Returns None once every cell has been carved
+    pub fn step(&mut self) -> Option<MazeCell> {
+        while let Some(&current) = self.stack.last() {
+            let unvisited: Vec<MazeCell> = self
+                .maze
+                .neighbors_of(current)
+                .into_iter()
+                .filter(|&cell| !self.visited[self.maze.cell_index(cell)])
+                .collect();
+
+            if unvisited.is_empty() {
+                // dead end, backtrack until a cell with unvisited neighbors is found
+                self.stack.pop();
+                continue;
+            }
+
+            let next = unvisited[self.rng.next_index(unvisited.len())];
+            self.maze.open_wall_between(current, next);
+            let index = self.maze.cell_index(next);
+            self.visited[index] = true;
+            self.stack.push(next);
+
+            return Some(next);
+        }
+
+        None
+    }
+
+    /// Carves any remaining passages and returns the completed maze
+    pub fn finish(mut self) -> Maze {
+        while self.step().is_some() {}
+
+        self.maze
+    }
+}
+
+impl<'a> BfsSolver<'a> {
+    pub fn new(maze: &'a Maze, start: MazeCell, goal: MazeCell) -> Self {
+        let mut distances = vec![vec![None; maze.height as usize]; maze.width as usize];
+        distances[start.x as usize][start.y as usize] = Some(0);
+
+        BfsSolver {
+            maze,
+            goal,
+            distances,
+            frontier: VecDeque::from(vec![start]),
+        }
+    }
+
+    /// Expands the next cell on the frontier, visiting each of its unvisited neighbors
+    /// Once the search is finished every step visits nothing.
+    pub fn step(&mut self) -> SolverStep {
+        let mut visited = vec![];
+        if let Some(cell) = self.frontier.pop_front() {
+            let distance = self.distance(cell).unwrap();
+            for next in self.maze.get_movement_options_for(cell).cells() {
+                let next_distance = &mut self.distances[next.x as usize][next.y as usize];
+                if next_distance.is_none() {
+                    *next_distance = Some(distance + 1);
+                    self.frontier.push_back(next);
+                    visited.push(next);
+                }
+            }
+        }
+
+        SolverStep {
+            visited,
+            found_goal: self.distance(self.goal).is_some(),
+        }
+    }
+
+    /// Returns true once every cell reachable from start has been expanded
+    pub fn is_finished(&self) -> bool {
+        self.frontier.is_empty()
+    }
+
+    /// Returns the distance from start, or None if the search hasn't reached cell
+    pub fn distance(&self, cell: MazeCell) -> Option<u32> {
+        self.distances[cell.x as usize][cell.y as usize]
+    }
+}
+
+impl<'a> SolvedMaze<'a> {
+    pub fn new(maze: &'a Maze, start: MazeCell) -> Self {
+        SolvedMaze {
+            maze,
+            start,
+            path: OnceCell::new(),
+            #[cfg(test)]
+            fill_count: std::cell::Cell::new(0),
+        }
+    }
+
+    /// Returns the shortest path distance from start, or None if cell is unreachable
+    pub fn distance(&self, cell: MazeCell) -> Option<u32> {
+        self.path().distance_to(cell)
+    }
+
+    /// Returns the shortest path from start to cell, including both endpoints
+    pub fn path_to(&self, cell: MazeCell) -> Option<Vec<MazeCell>> {
+        self.path().path_to(cell)
+    }
+
+    fn path(&self) -> &MazePath {
+        self.path.get_or_init(|| {
+            #[cfg(test)]
+            self.fill_count.set(self.fill_count.get() + 1);
+            MazePath::new(self.maze, self.start)
+        })
+    }
+}
+
+impl MazePath {
+    /// Floods the maze breadth first from start
+    fn new(maze: &Maze, start: MazeCell) -> Self {
+        let mut distances = vec![vec![None; maze.height as usize]; maze.width as usize];
+        let mut parents = vec![vec![None; maze.height as usize]; maze.width as usize];
+
+        distances[start.x as usize][start.y as usize] = Some(0);
+        let mut frontier = VecDeque::new();
+        frontier.push_back((start, 0));
+        let mut visit_order = vec![];
+
+        while let Some((cell, distance)) = frontier.pop_front() {
+            visit_order.push(cell);
+            for next in maze.get_movement_options_for(cell).cells() {
+                let (x, y) = (next.x as usize, next.y as usize);
+                if distances[x][y].is_none() {
+                    distances[x][y] = Some(distance + 1);
+                    parents[x][y] = Some(cell);
+                    frontier.push_back((next, distance + 1));
+                }
+            }
+        }
+
+        MazePath {
+            start,
+            distances,
+            parents,
+            visit_order,
+        }
+    }
+
+    fn distance_to(&self, cell: MazeCell) -> Option<u32> {
+        self.distances[cell.x as usize][cell.y as usize]
+    }
+
+    /// Returns the shortest path from start to the given cell, including both endpoints
+    fn path_to(&self, end: MazeCell) -> Option<Vec<MazeCell>> {
+        self.distance_to(end)?;
+
+        let mut path = vec![end];
+        let mut current = end;
+        while current != self.start {
+            current = self.parent_of(current)?;
+            path.push(current);
+        }
+        path.reverse();
+
+        Some(path)
+    }
+
+    /// Returns None for the start cell and for unreachable cells
+    fn parent_of(&self, cell: MazeCell) -> Option<MazeCell> {
+        self.parents[cell.x as usize][cell.y as usize]
+    }
+}
+
+impl MovementOptions {
+    fn new(
+        north: Option<MazeCell>,
+        east: Option<MazeCell>,
+        south: Option<MazeCell>,
+        west: Option<MazeCell>,
+    ) -> Self {
+        MovementOptions {
+            north,
+            east,
             south,
             west,
         }
     }
-}
 
-impl MazeCell {
-    fn new(x: u32, y: u32) -> Self {
-        MazeCell { x, y }
+    /// Returns the reachable cells in north, east, south, west order
+    fn cells(&self) -> Vec<MazeCell> {
+        [self.north, self.east, self.south, self.west]
+            .iter()
+            .filter_map(|&cell| cell)
+            .collect()
+    }
+}
+
+impl MazeCell {
+    pub fn new(x: u32, y: u32) -> Self {
+        MazeCell { x, y }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use insta::assert_display_snapshot_matches;
+
+    /// Replays fixed values in place of real randomness
+    struct MockRng<F1, F2> {
+        rand_bool: F1,
+        rand_usize: F2,
+    }
+
+    impl<F1, F2> MockRng<F1, F2>
+    where
+        F1: FnMut() -> bool,
+        F2: FnMut() -> usize,
+    {
+        fn new(rand_bool: F1, rand_usize: F2) -> Self {
+            MockRng {
+                rand_bool,
+                rand_usize,
+            }
+        }
+    }
+
+    impl<F1, F2> MazeRng for MockRng<F1, F2>
+    where
+        F1: FnMut() -> bool,
+        F2: FnMut() -> usize,
+    {
+        fn next_bool(&mut self) -> bool {
+            (self.rand_bool)()
+        }
+
+        fn next_index(&mut self, n: usize) -> usize {
+            (self.rand_usize)() % n
+        }
+    }
+
+    #[test]
+    fn new_3x3() {
+        let maze = Maze::new(3, 3);
+
+        assert_display_snapshot_matches!(maze);
+    }
+
+    #[test]
+    fn wall_indexes_for_cell_00() {
+        let maze = Maze::new(3, 3);
+        let x = 0;
+        let y = 0;
+
+        assert_eq!(Some(0), maze.north_wall_index_for_cell(x, y));
+        assert_eq!(Some(6), maze.east_wall_index_for_cell(x, y));
+        assert_eq!(None, maze.south_wall_index_for_cell(x, y));
+        assert_eq!(None, maze.west_wall_index_for_cell(x, y));
+    }
+
+    #[test]
+    fn wall_indexes_for_cell_11() {
+        let maze = Maze::new(3, 3);
+        let x = 1;
+        let y = 1;
+
+        assert_eq!(Some(4), maze.north_wall_index_for_cell(x, y));
+        assert_eq!(Some(10), maze.east_wall_index_for_cell(x, y));
+        assert_eq!(Some(1), maze.south_wall_index_for_cell(x, y));
+        assert_eq!(Some(7), maze.west_wall_index_for_cell(x, y));
+    }
+
+    #[test]
+    fn wall_indexes_for_cell_22() {
+        let maze = Maze::new(3, 3);
+        let x = 2;
+        let y = 2;
+
+        assert_eq!(None, maze.north_wall_index_for_cell(x, y));
+        assert_eq!(None, maze.east_wall_index_for_cell(x, y));
+        assert_eq!(Some(5), maze.south_wall_index_for_cell(x, y));
+        assert_eq!(Some(11), maze.west_wall_index_for_cell(x, y));
+    }
+
+    #[test]
+    fn open_north_wall() {
+        let mut maze = Maze::new(3, 3);
+        let mut maze_iter = MazeIterator::new(&maze);
+        let cell = maze_iter.next().unwrap();
+
+        maze.open_north_wall(cell).unwrap();
+
+        assert_display_snapshot_matches!(maze);
+    }
+
+    #[test]
+    fn open_east_wall() {
+        let mut maze = Maze::new(3, 3);
+        let mut maze_iter = MazeIterator::new(&maze);
+        let cell = maze_iter.next().unwrap();
+
+        maze.open_east_wall(cell).unwrap();
+
+        assert_display_snapshot_matches!(maze);
+    }
+
+    #[test]
+    fn next_cell_open_north_wall() {
+        let mut maze = Maze::new(3, 3);
+        let mut maze_iter = MazeIterator::new(&maze);
+        maze_iter.next().unwrap(); // skip the first cell
+        let cell = maze_iter.next().unwrap();
+
+        maze.open_north_wall(cell).unwrap();
+
+        assert_display_snapshot_matches!(maze);
+    }
+
+    #[test]
+    fn next_cell_open_east_wall() {
+        let mut maze = Maze::new(3, 3);
+        let mut maze_iter = MazeIterator::new(&maze);
+        maze_iter.next().unwrap(); // skip the first cell
+        let cell = maze_iter.next().unwrap();
+
+        maze.open_east_wall(cell).unwrap();
+
+        assert_display_snapshot_matches!(maze);
+    }
+
+    #[test]
+    fn move_to_next_cell_wraps() {
+        let maze = Maze::new(3, 3);
+        let mut maze_iter = MazeIterator::new(&maze);
+        maze_iter.next().unwrap(); // Position: 0, 0
+        maze_iter.next().unwrap(); // Position: 1, 0
+        maze_iter.next().unwrap(); // Position: 2, 0
+        let cell = maze_iter.next().unwrap(); // Position: 0, 1
+
+        assert_eq!(0, cell.x);
+        assert_eq!(1, cell.y);
+    }
+
+    #[test]
+    fn move_to_next_cell_returns_none() {
+        let maze = Maze::new(3, 3);
+        let mut maze_iter = MazeIterator::new(&maze);
+        maze_iter.next().unwrap(); // Position: 0, 0
+        maze_iter.next().unwrap(); // Position: 1, 0
+        maze_iter.next().unwrap(); // Position: 2, 0
+        maze_iter.next().unwrap(); // Position: 0, 1
+        maze_iter.next().unwrap(); // Position: 1, 1
+        maze_iter.next().unwrap(); // Position: 2, 1
+        maze_iter.next().unwrap(); // Position: 0, 2
+        maze_iter.next().unwrap(); // Position: 1, 2
+        maze_iter.next().unwrap(); // Position: 2, 2
+        let cell = maze_iter.next();
+
+        assert!(cell.is_none());
+    }
+
+    #[test]
+    fn binary_tree_all_true() {
+        let mock_rand_bool = || true;
+        let maze = Maze::binary_tree_with_rng(3, 3, &mut MockRng::new(mock_rand_bool, || 0));
+
+        assert_display_snapshot_matches!(maze);
+    }
+
+    #[test]
+    fn binary_tree_all_false() {
+        let mock_rand_bool = || false;
+        let maze = Maze::binary_tree_with_rng(3, 3, &mut MockRng::new(mock_rand_bool, || 0));
+
+        assert_display_snapshot_matches!(maze);
+    }
+
+    #[test]
+    fn binary_tree_alternating_bool() {
+        let mut val = false;
+        let mock_rand_bool = || {
+            val = !val;
+            val
+        };
+        let maze = Maze::binary_tree_with_rng(3, 3, &mut MockRng::new(mock_rand_bool, || 0));
+
+        assert_display_snapshot_matches!(maze);
+    }
+
+    #[test]
+    fn binary_tree_config_fallback_order_matters() {
+        let build = |primary, fallback| {
+            let mut val = false;
+            let mock_rand_bool = move || {
+                val = !val;
+                val
+            };
+            let mut rng = MockRng::new(mock_rand_bool, || 0);
+            Maze::binary_tree_config(3, 3, primary, fallback, &mut rng)
+        };
+
+        let north_east = build(Direction::North, Direction::East);
+        let east_north = build(Direction::East, Direction::North);
+
+        assert_ne!(north_east, east_north);
+        assert!(is_perfect(&east_north));
+        assert!(is_perfect(&build(Direction::South, Direction::West)));
+    }
+
+    #[test]
+    fn sidewinder_all_true() {
+        let mock_rand_bool = || true;
+        let mock_rand_u32 = || 0_usize;
+        let maze =
+            Maze::sidewinder_with_rng(3, 3, &mut MockRng::new(mock_rand_bool, mock_rand_u32));
+
+        assert_display_snapshot_matches!(maze);
+    }
+
+    #[test]
+    fn sidewinder_all_false() {
+        let mock_rand_bool = || false;
+        let mock_rand_u32 = || 0_usize;
+        let maze =
+            Maze::sidewinder_with_rng(3, 3, &mut MockRng::new(mock_rand_bool, mock_rand_u32));
+
+        assert_display_snapshot_matches!(maze);
+    }
+
+    #[test]
+    fn sidewinder_alternating_bool_0usize() {
+        let mut val = false;
+        let mock_rand_bool = || {
+            val = !val;
+            val
+        };
+        let mock_rand_u32 = || 0_usize;
+        let maze =
+            Maze::sidewinder_with_rng(3, 3, &mut MockRng::new(mock_rand_bool, mock_rand_u32));
+
+        assert_display_snapshot_matches!(maze);
+    }
+
+    #[test]
+    fn sidewinder_top_row_is_corridor() {
+        let mut val = true;
+        let mock_rand_bool = || {
+            val = !val;
+            val
+        };
+        let mock_rand_u32 = || 0_usize;
+        let maze =
+            Maze::sidewinder_with_rng(3, 3, &mut MockRng::new(mock_rand_bool, mock_rand_u32));
+
+        for x in 0..2 {
+            assert_eq!(
+                Wall::Open,
+                maze.walls[maze.east_wall_index_for_cell(x, 2).unwrap()]
+            );
+        }
+    }
+
+    fn build_sidewinder_alternating_bool_1usize() -> Maze {
+        let mut val = false;
+        let mock_rand_bool = || {
+            val = !val;
+            val
+        };
+        let mock_rand_u32 = || 1_usize;
+        Maze::sidewinder_with_rng(3, 3, &mut MockRng::new(mock_rand_bool, mock_rand_u32))
+    }
+
+    #[test]
+    fn sidewinder_alternating_bool_1usize() {
+        let maze = build_sidewinder_alternating_bool_1usize();
+
+        assert_display_snapshot_matches!(maze);
+    }
+
+    #[test]
+    fn get_movement_options_for_sidewinder_00() {
+        let maze = build_sidewinder_alternating_bool_1usize();
+
+        let cell = MazeCell::new(0, 0);
+        let expected_movement_options =
+            MovementOptions::new(Some(MazeCell::new(0, 1)), None, None, None);
+        assert_eq!(
+            expected_movement_options,
+            maze.get_movement_options_for(cell)
+        );
+    }
+
+    #[test]
+    fn open_neighbors_match_movement_options() {
+        let maze = Maze::generate_with_deadend_ratio(5, 5, 0.1, 3);
+
+        for cell in MazeIterator::new(&maze) {
+            assert_eq!(
+                maze.get_movement_options_for(cell).cells(),
+                maze.open_neighbors(cell).collect::<Vec<MazeCell>>()
+            );
+        }
+    }
+
+    #[test]
+    fn visible_cells_follow_straight_open_passages() {
+        // ┌───┬───┬───┐
+        // │   │   │   │
+        // ├───┤   ├───┤
+        // │           │
+        // ├───┼───┤   │
+        // │   │   │   │
+        // └───┴───┴───┘
+        let mut maze = Maze::new(3, 3);
+        maze.open_east_wall(MazeCell::new(0, 1)).unwrap();
+        maze.open_east_wall(MazeCell::new(1, 1)).unwrap();
+        maze.open_north_wall(MazeCell::new(1, 1)).unwrap();
+        maze.open_north_wall(MazeCell::new(2, 0)).unwrap();
+
+        let visible = maze.visible_cells(MazeCell::new(1, 1));
+
+        assert_eq!(
+            vec![
+                MazeCell::new(1, 1),
+                MazeCell::new(1, 2),
+                MazeCell::new(2, 1),
+                MazeCell::new(0, 1),
+            ],
+            visible
+        );
+    }
+
+    #[test]
+    fn visible_cells_do_not_turn_corners() {
+        let mut maze = Maze::new(3, 3);
+        maze.open_east_wall(MazeCell::new(0, 1)).unwrap();
+        maze.open_east_wall(MazeCell::new(1, 1)).unwrap();
+        maze.open_north_wall(MazeCell::new(1, 1)).unwrap();
+        maze.open_north_wall(MazeCell::new(2, 0)).unwrap();
+
+        let visible = maze.visible_cells(MazeCell::new(0, 1));
+
+        // (2, 0) is reachable through (2, 1) but is around a corner
+        assert_eq!(
+            vec![
+                MazeCell::new(0, 1),
+                MazeCell::new(1, 1),
+                MazeCell::new(2, 1),
+            ],
+            visible
+        );
+    }
+
+    #[test]
+    fn wall_segments_count_closed_walls_and_border() {
+        let maze = build_sidewinder_alternating_bool_1usize();
+        let closed_walls = maze
+            .walls
+            .iter()
+            .filter(|wall| match wall {
+                Wall::Open => false,
+                Wall::Closed => true,
+            })
+            .count();
+        let border_segments = 2 * (maze.width + maze.height) as usize;
+
+        assert_eq!(closed_walls + border_segments, maze.wall_segments().len());
+    }
+
+    #[test]
+    fn wall_segments_for_single_open_wall() {
+        let mut maze = Maze::new(2, 1);
+        maze.open_east_wall(MazeCell::new(0, 0)).unwrap();
+
+        assert_eq!(
+            vec![
+                ((0, 0), (1, 0)),
+                ((0, 1), (1, 1)),
+                ((1, 0), (2, 0)),
+                ((1, 1), (2, 1)),
+                ((0, 0), (0, 1)),
+                ((2, 0), (2, 1)),
+            ],
+            maze.wall_segments()
+        );
+    }
+
+    #[test]
+    fn to_wall_path_svg_merges_straight_walls() {
+        let maze = Maze::new(3, 1);
+        let svg = maze.to_wall_path_svg(10.0);
+
+        // the three cells along the bottom border make one path
+        assert_eq!(1, svg.matches(r#"d="M0 10 L30 10""#).count());
+        // each border side plus the two walls between cells
+        assert_eq!(6, svg.matches("<path").count());
+    }
+
+    #[test]
+    fn to_image_styled_wall_color() {
+        let maze = Maze::new(2, 1);
+        let style = ImageStyle {
+            cell_size: 10,
+            wall_thickness: 2.0,
+            ..ImageStyle::default()
+        };
+        let red = ImageStyle {
+            wall_color: [255, 0, 0],
+            ..style
+        };
+
+        let image = maze.to_image_styled(style);
+
+        assert_eq!((22, 12), (image.width(), image.height()));
+        // the wall between the two cells runs down column 10 and 11
+        assert_eq!([0, 0, 0], image.pixel(10, 5));
+        assert_eq!([255, 255, 255], image.pixel(5, 5));
+        assert_eq!([255, 0, 0], maze.to_image_styled(red).pixel(10, 5));
+    }
+
+    #[test]
+    fn to_image_styled_wall_thickness() {
+        let maze = Maze::new(2, 1);
+        let wall_columns = |thickness| {
+            let style = ImageStyle {
+                cell_size: 10,
+                wall_thickness: thickness,
+                ..ImageStyle::default()
+            };
+            let image = maze.to_image_styled(style);
+            let row = image.height() / 2;
+            (0..image.width())
+                .filter(|&x| image.pixel(x, row) == [0, 0, 0])
+                .count()
+        };
+
+        // two border walls and the wall between the cells
+        assert_eq!(6, wall_columns(2.0));
+        assert_eq!(12, wall_columns(4.0));
+    }
+
+    #[test]
+    fn to_image_styled_blends_fractional_walls() {
+        let style = ImageStyle {
+            cell_size: 10,
+            wall_thickness: 1.5,
+            ..ImageStyle::default()
+        };
+
+        let image = Maze::new(2, 1).to_image_styled(style);
+
+        // the wall between the cells covers all of column 10 and half of column 11
+        assert_eq!([0, 0, 0], image.pixel(10, 5));
+        assert_eq!([128, 128, 128], image.pixel(11, 5));
+        assert_eq!([255, 255, 255], image.pixel(12, 5));
+    }
+
+    #[test]
+    fn recursive_backtracker_seeded() {
+        let maze = Maze::recursive_backtracker_seeded(4, 4, 0);
+
+        assert_display_snapshot_matches!(maze);
+    }
+
+    #[test]
+    fn recursive_backtracker_gen_steps_once_per_cell() {
+        let mut generator = RecursiveBacktrackerGen::new(4, 3, 7);
+        let mut steps = 0;
+        while generator.step().is_some() {
+            steps += 1;
+        }
+
+        // every cell except the starting cell is carved into exactly once
+        assert_eq!(4 * 3 - 1, steps);
+    }
+
+    #[test]
+    fn recursive_backtracker_gen_matches_one_shot() {
+        let mut generator = RecursiveBacktrackerGen::new(5, 4, 42);
+        while generator.step().is_some() {}
+
+        assert_eq!(
+            Maze::recursive_backtracker_seeded(5, 4, 42),
+            generator.finish()
+        );
+    }
+
+    #[test]
+    fn render_viewport_2x2() {
+        let maze = Maze::recursive_backtracker_seeded(4, 4, 0);
+
+        assert_display_snapshot_matches!(maze.render_viewport(1, 1, 2, 2).unwrap());
+    }
+
+    #[test]
+    fn render_viewport_of_whole_maze_matches_display() {
+        let maze = Maze::recursive_backtracker_seeded(4, 4, 0);
+
+        assert_eq!(Ok(maze.to_string()), maze.render_viewport(0, 0, 4, 4));
+    }
+
+    #[test]
+    fn render_viewport_outside_maze() {
+        let maze = Maze::new(4, 4);
+
+        assert_eq!(
+            Err(MazeError::RectOutOfBounds(Rect::new(3, 0, 2, 2))),
+            maze.render_viewport(3, 0, 2, 2)
+        );
+        assert_eq!(
+            Err(MazeError::RectOutOfBounds(Rect::new(u32::MAX, 0, 2, 1))),
+            maze.render_viewport(u32::MAX, 0, 2, 1)
+        );
+        assert_eq!(
+            Err(MazeError::RectOutOfBounds(Rect::new(0, 0, 0, 1))),
+            maze.render_viewport(0, 0, 0, 1)
+        );
+    }
+
+    #[test]
+    fn spanning_tree_from_leads_to_root() {
+        let maze = Maze::recursive_backtracker_seeded(5, 5, 9);
+        let root = MazeCell::new(2, 2);
+
+        let parents = maze.spanning_tree_from(root);
+
+        assert_eq!(None, parents[2][2]);
+        for cell in MazeIterator::new(&maze) {
+            let mut current = cell;
+            let mut steps = 0;
+            while let Some(parent) = parents[current.x as usize][current.y as usize] {
+                current = parent;
+                steps += 1;
+            }
+            assert_eq!(root, current);
+            assert_eq!(maze.solution_length(cell, root), Some(steps));
+        }
+    }
+
+    #[test]
+    fn cell_traffic_is_highest_at_start_and_corridor() {
+        // ┌───┬───────────┐
+        // │   │           │
+        // │   └───╴   ╶───┤
+        // │               │
+        // └───────────────┘
+        let mut maze = Maze::new(4, 2);
+        maze.open_north_wall(MazeCell::new(0, 0)).unwrap();
+        maze.open_east_wall(MazeCell::new(0, 0)).unwrap();
+        maze.open_east_wall(MazeCell::new(1, 0)).unwrap();
+        maze.open_east_wall(MazeCell::new(2, 0)).unwrap();
+        maze.open_north_wall(MazeCell::new(2, 0)).unwrap();
+        maze.open_east_wall(MazeCell::new(1, 1)).unwrap();
+        maze.open_east_wall(MazeCell::new(2, 1)).unwrap();
+
+        // every other cell reaches the exit at (0, 1) through the start and then along
+        // the bottom corridor
+        let traffic = maze.cell_traffic(MazeCell::new(0, 0), MazeCell::new(0, 1));
+
+        assert_eq!(
+            vec![vec![7, 8], vec![6, 1], vec![5, 3], vec![1, 1]],
+            traffic
+        );
+        assert_eq!(
+            vec![vec![0], vec![0]],
+            Maze::new(2, 1).cell_traffic(MazeCell::new(0, 0), MazeCell::new(1, 0))
+        );
+    }
+
+    #[test]
+    fn solve_sidewinder() {
+        let maze = build_sidewinder_alternating_bool_1usize();
+
+        assert_eq!(
+            Some(vec![
+                MazeCell::new(0, 0),
+                MazeCell::new(0, 1),
+                MazeCell::new(1, 1),
+                MazeCell::new(1, 2),
+                MazeCell::new(2, 2),
+            ]),
+            maze.solve(MazeCell::new(0, 0), MazeCell::new(2, 2))
+        );
+    }
+
+    #[test]
+    fn bfs_solver_matches_maze_path() {
+        let maze = Maze::recursive_backtracker_seeded(6, 5, 12);
+        let (start, goal) = (MazeCell::new(0, 0), MazeCell::new(5, 4));
+        let mut solver = BfsSolver::new(&maze, start, goal);
+
+        let mut found_goal = false;
+        while !solver.is_finished() {
+            let step = solver.step();
+            if step.visited.contains(&goal) {
+                found_goal = true;
+            }
+            assert_eq!(found_goal, step.found_goal);
+        }
+
+        let path = MazePath::new(&maze, start);
+        for cell in MazeIterator::new(&maze) {
+            assert_eq!(path.distance_to(cell), solver.distance(cell));
+        }
+        assert!(solver.step().visited.is_empty());
+    }
+
+    #[test]
+    fn solved_maze_fills_once() {
+        let maze = Maze::recursive_backtracker_seeded(5, 5, 2);
+        let (start, end) = (MazeCell::new(0, 0), MazeCell::new(4, 4));
+        let solved = SolvedMaze::new(&maze, start);
+        assert_eq!(0, solved.fill_count.get());
+
+        let path = solved.path_to(end).unwrap();
+        assert_eq!(Some(path.len() as u32 - 1), solved.distance(end));
+        assert_eq!(maze.solve(start, end), Some(path));
+        assert_eq!(Some(0), solved.distance(start));
+        assert_eq!(1, solved.fill_count.get());
+    }
+
+    #[test]
+    fn solve_detailed_errors() {
+        let maze = build_2x2();
+        let (inside, outside) = (MazeCell::new(0, 0), MazeCell::new(2, 0));
+
+        assert_eq!(
+            Err(SolveError::StartOutOfBounds(outside)),
+            maze.solve_detailed(outside, inside)
+        );
+        assert_eq!(
+            Err(SolveError::EndOutOfBounds(outside)),
+            maze.solve_detailed(inside, outside)
+        );
+        assert_eq!(
+            Err(SolveError::Unreachable),
+            Maze::new(2, 2).solve_detailed(inside, MazeCell::new(1, 1))
+        );
+        assert_eq!(
+            Ok(vec![inside, MazeCell::new(0, 1), MazeCell::new(1, 1)]),
+            maze.solve_detailed(inside, MazeCell::new(1, 1))
+        );
+    }
+
+    #[test]
+    fn solution_length_matches_solve() {
+        let maze = Maze::recursive_backtracker_seeded(6, 6, 4);
+        let (start, end) = (MazeCell::new(0, 0), MazeCell::new(5, 5));
+
+        assert_eq!(
+            maze.solve(start, end).unwrap().len() as u32 - 1,
+            maze.solution_length(start, end).unwrap()
+        );
+    }
+
+    #[test]
+    fn solution_fraction_2x2() {
+        let maze = build_2x2();
+
+        // the path (0, 0), (0, 1), (1, 1) covers three of the four cells
+        assert_eq!(
+            0.75,
+            maze.solution_fraction(MazeCell::new(0, 0), MazeCell::new(1, 1))
+        );
+        assert_eq!(
+            0.0,
+            Maze::new(2, 2).solution_fraction(MazeCell::new(0, 0), MazeCell::new(1, 1))
+        );
+    }
+
+    #[test]
+    fn solve_unreachable() {
+        let maze = Maze::new(3, 3);
+
+        assert_eq!(None, maze.solve(MazeCell::new(0, 0), MazeCell::new(2, 2)));
+    }
+
+    #[test]
+    fn solve_weighted_constant_weight_matches_solve() {
+        let maze = Maze::recursive_backtracker_seeded(6, 6, 3);
+        let start = MazeCell::new(0, 0);
+        let end = MazeCell::new(5, 5);
+
+        assert_eq!(
+            maze.solve(start, end),
+            maze.solve_weighted(start, end, |_cell| 1)
+        );
+    }
+
+    #[test]
+    fn solve_weighted_avoids_expensive_cells() {
+        // ┌───────────┐
+        // │           │
+        // │   ╶───╴   │
+        // │           │
+        // └───────────┘
+        let mut maze = Maze::new(3, 2);
+        maze.open_east_wall(MazeCell::new(0, 0)).unwrap();
+        maze.open_east_wall(MazeCell::new(1, 0)).unwrap();
+        maze.open_east_wall(MazeCell::new(0, 1)).unwrap();
+        maze.open_east_wall(MazeCell::new(1, 1)).unwrap();
+        maze.open_north_wall(MazeCell::new(0, 0)).unwrap();
+        maze.open_north_wall(MazeCell::new(2, 0)).unwrap();
+        let start = MazeCell::new(0, 0);
+        let end = MazeCell::new(2, 0);
+        let expensive = MazeCell::new(1, 0);
+
+        let path = maze
+            .solve_weighted(start, end, |cell| if cell == expensive { 100 } else { 1 })
+            .unwrap();
+
+        assert_eq!(Some(3), maze.solve(start, end).map(|path| path.len()));
+        assert_eq!(
+            vec![
+                MazeCell::new(0, 0),
+                MazeCell::new(0, 1),
+                MazeCell::new(1, 1),
+                MazeCell::new(2, 1),
+                MazeCell::new(2, 0),
+            ],
+            path
+        );
+    }
+
+    #[test]
+    fn to_flat() {
+        let mut maze = Maze::new(3, 3);
+        maze.open_north_wall(MazeCell::new(1, 1)).unwrap();
+        maze.open_east_wall(MazeCell::new(0, 0)).unwrap();
+
+        let (width, height, walls) = maze.to_flat();
+
+        assert_eq!(3, width);
+        assert_eq!(3, height);
+        assert_eq!(maze.walls.len(), walls.len());
+        assert_eq!(vec![1, 1, 1, 1, 0, 1, 0, 1, 1, 1, 1, 1], walls);
+    }
+
+    /// A perfect maze has exactly one path between any two cells
+    fn is_perfect(maze: &Maze) -> bool {
+        let open_walls = maze
+            .walls
+            .iter()
+            .filter(|&&wall| wall == Wall::Open)
+            .count();
+        let num_cells = (maze.width * maze.height) as usize;
+        let path = MazePath::new(maze, MazeCell::new(0, 0));
+        let all_reachable = MazeIterator::new(maze).all(|cell| path.distance_to(cell).is_some());
+
+        all_reachable && open_walls == num_cells - 1
+    }
+
+    #[test]
+    fn carve_edges_spanning_tree_is_perfect() {
+        let mut maze = Maze::new(3, 2);
+        let edges = [
+            (MazeCell::new(0, 0), MazeCell::new(1, 0)),
+            (MazeCell::new(1, 0), MazeCell::new(1, 1)),
+            (MazeCell::new(0, 1), MazeCell::new(1, 1)),
+            (MazeCell::new(2, 1), MazeCell::new(1, 1)),
+            (MazeCell::new(2, 1), MazeCell::new(2, 0)),
+        ];
+
+        maze.carve_edges(&edges).unwrap();
+
+        assert!(is_perfect(&maze));
+    }
+
+    #[test]
+    fn connect_opens_shared_wall() {
+        let mut maze = Maze::new(3, 3);
+
+        maze.connect(MazeCell::new(0, 0), MazeCell::new(1, 0))
+            .unwrap();
+
+        assert_eq!(
+            Wall::Open,
+            maze.walls[maze.east_wall_index_for_cell(0, 0).unwrap()]
+        );
+        assert_eq!(
+            Err(MazeError::NotAdjacent(
+                MazeCell::new(0, 0),
+                MazeCell::new(2, 0)
+            )),
+            maze.connect(MazeCell::new(0, 0), MazeCell::new(2, 0))
+        );
+    }
+
+    #[test]
+    fn carve_edges_not_adjacent() {
+        let mut maze = Maze::new(3, 2);
+        let edges = [
+            (MazeCell::new(0, 0), MazeCell::new(1, 0)),
+            (MazeCell::new(0, 0), MazeCell::new(1, 1)),
+        ];
+
+        assert_eq!(
+            Err(MazeError::NotAdjacent(
+                MazeCell::new(0, 0),
+                MazeCell::new(1, 1)
+            )),
+            maze.carve_edges(&edges)
+        );
+        assert_eq!(Maze::new(3, 2), maze);
+    }
+
+    #[test]
+    fn carve_edges_out_of_bounds() {
+        let mut maze = Maze::new(3, 2);
+        let edges = [(MazeCell::new(2, 1), MazeCell::new(3, 1))];
+
+        assert_eq!(
+            Err(MazeError::CellOutOfBounds(MazeCell::new(3, 1))),
+            maze.carve_edges(&edges)
+        );
+    }
+
+    #[test]
+    fn to_rle_round_trips() {
+        let maze = Maze::recursive_backtracker_seeded(7, 5, 3);
+
+        assert_eq!(Ok(maze.clone()), Maze::from_rle(&maze.to_rle()));
+    }
+
+    #[test]
+    fn to_rle_all_closed_is_single_run() {
+        let maze = Maze::new(4, 3);
+
+        assert_eq!("4x3:17C", maze.to_rle());
+        assert_eq!(Ok(maze), Maze::from_rle("4x3:17C"));
+    }
+
+    #[test]
+    fn from_rle_errors() {
+        assert_eq!(Err(ParseError::InvalidDimensions), Maze::from_rle("4x0:"));
+        assert_eq!(
+            Err(ParseError::WrongWallCount(17, 16)),
+            Maze::from_rle("4x3:16C")
+        );
+        assert_eq!(
+            Err(ParseError::UnexpectedChar('X', 0, 5)),
+            Maze::from_rle("4x3:1X16C")
+        );
+        assert_eq!(
+            Err(ParseError::WrongWallCount(0, usize::MAX)),
+            Maze::from_rle("1x1:18446744073709551615C")
+        );
+        assert_eq!(
+            Err(ParseError::InvalidRun(4)),
+            Maze::from_rle("1x1:99999999999999999999C")
+        );
+        assert_eq!(Err(ParseError::InvalidRun(4)), Maze::from_rle("1x1:0C"));
+        assert_eq!(Err(ParseError::InvalidRun(5)), Maze::from_rle("2x1:O5"));
+        assert_eq!(
+            Err(ParseError::WrongWallCount(17, 18)),
+            Maze::from_rle("4x3:16C2O")
+        );
+    }
+
+    #[test]
+    fn bottleneck_cells_of_perfect_maze_is_solution() {
+        let maze = Maze::recursive_backtracker_seeded(6, 6, 4);
+        let (start, end) = (MazeCell::new(0, 0), MazeCell::new(5, 5));
+
+        assert_eq!(
+            maze.solve(start, end).unwrap(),
+            maze.bottleneck_cells(start, end)
+        );
+    }
+
+    #[test]
+    fn bottleneck_cells_of_loop_with_tail() {
+        let maze = build_loop_with_tail();
+
+        // the loop can be walked either way around, but both ways meet at (1, 0)
+        assert_eq!(
+            vec![
+                MazeCell::new(0, 1),
+                MazeCell::new(1, 0),
+                MazeCell::new(2, 0),
+                MazeCell::new(2, 1),
+            ],
+            maze.bottleneck_cells(MazeCell::new(0, 1), MazeCell::new(2, 1))
+        );
+        assert!(Maze::new(2, 1)
+            .bottleneck_cells(MazeCell::new(0, 0), MazeCell::new(1, 0))
+            .is_empty());
+    }
+
+    #[test]
+    fn solution_branching_of_loop_with_tail() {
+        let maze = build_loop_with_tail();
+
+        // the path runs along the bottom row and up, passing the loop's north turns at
+        // (0, 0) and (1, 0)
+        assert_eq!(
+            0.5,
+            maze.solution_branching(MazeCell::new(0, 0), MazeCell::new(2, 1))
+        );
+        assert_eq!(
+            0.0,
+            Maze::new(2, 1).solution_branching(MazeCell::new(0, 0), MazeCell::new(1, 0))
+        );
+    }
+
+    #[test]
+    fn loop_density_of_loop_with_tail() {
+        let maze = build_loop_with_tail();
+
+        assert_eq!(
+            vec![vec![1, 1], vec![1, 1], vec![0, 0]],
+            maze.loop_density()
+        );
+
+        let perfect = Maze::recursive_backtracker_seeded(4, 4, 1);
+        assert_eq!(vec![vec![0; 4]; 4], perfect.loop_density());
+    }
+
+    #[test]
+    fn articulation_points_of_loop_with_tail() {
+        let maze = build_loop_with_tail();
+
+        assert_eq!(
+            vec![MazeCell::new(1, 0), MazeCell::new(2, 0)],
+            maze.articulation_points()
+        );
+    }
+
+    #[test]
+    fn articulation_points_of_corridor() {
+        let mut maze = Maze::new(3, 1);
+        maze.open_east_wall(MazeCell::new(0, 0)).unwrap();
+        maze.open_east_wall(MazeCell::new(1, 0)).unwrap();
+
+        assert_eq!(vec![MazeCell::new(1, 0)], maze.articulation_points());
+    }
+
+    #[test]
+    fn from_zones_sidewinder_and_recursive_backtracker() {
+        let left = Rect::new(0, 0, 4, 4);
+        let right = Rect::new(4, 0, 4, 4);
+        let zones = vec![
+            (left, Algorithm::Sidewinder),
+            (right, Algorithm::RecursiveBacktracker),
+        ];
+        let maze = Maze::from_zones(8, 4, zones.clone(), 3).unwrap();
+
+        assert_eq!(Ok(maze.clone()), Maze::from_zones(8, 4, zones, 3));
+
+        // sidewinder always carves a corridor along the top row of its zone
+        for x in 0..3 {
+            assert_eq!(
+                Wall::Open,
+                maze.walls[maze.east_wall_index_for_cell(x, 3).unwrap()]
+            );
+        }
+        // each zone is itself a perfect maze
+        assert!(is_perfect(&maze.sub_maze(Rect::new(0, 0, 4, 4))));
+        assert!(is_perfect(&maze.sub_maze(Rect::new(4, 0, 4, 4))));
+        assert!(is_perfect(&maze));
+    }
+
+    #[test]
+    fn from_zones_overlapping() {
+        let result = Maze::from_zones(
+            4,
+            4,
+            vec![
+                (Rect::new(0, 0, 3, 4), Algorithm::BinaryTree),
+                (Rect::new(2, 0, 2, 4), Algorithm::BinaryTree),
+            ],
+            0,
+        );
+
+        assert_eq!(
+            Err(MazeError::RectsOverlap(
+                Rect::new(0, 0, 3, 4),
+                Rect::new(2, 0, 2, 4)
+            )),
+            result
+        );
+    }
+
+    #[test]
+    fn from_zones_not_covered() {
+        let result = Maze::from_zones(
+            4,
+            4,
+            vec![(Rect::new(0, 0, 4, 3), Algorithm::BinaryTree)],
+            0,
+        );
+
+        assert_eq!(Err(MazeError::CellNotCovered(MazeCell::new(0, 3))), result);
+    }
+
+    #[test]
+    fn from_weighted_edges_constant_weight_is_perfect() {
+        let maze = Maze::from_weighted_edges(6, 4, |_a, _b| 1.0);
+
+        assert!(is_perfect(&maze));
+    }
+
+    #[test]
+    fn from_weighted_edges_2x2() {
+        // the north wall of (1, 0) is the most expensive, so it is the one left closed
+        let maze = Maze::from_weighted_edges(2, 2, |a, b| match (a.x, b.x) {
+            (0, 0) => 0.0,
+            (1, 1) => 2.0,
+            _ => 1.0,
+        });
+
+        assert_eq!(build_2x2(), maze);
+    }
+
+    #[test]
+    fn onion_follows_rings() {
+        let onion = Maze::onion(12, 12, 5);
+        let backtracker = Maze::recursive_backtracker_seeded(12, 12, 5);
+
+        assert!(is_perfect(&onion));
+        assert!(onion.river_factor() > backtracker.river_factor());
+    }
+
+    #[test]
+    fn generate_around_path_solves_along_path() {
+        let path: Vec<MazeCell> = [(0, 0), (1, 0), (1, 1), (1, 2), (2, 2), (3, 2), (3, 3)]
+            .iter()
+            .map(|&(x, y)| MazeCell::new(x, y))
+            .collect();
+
+        let maze = Maze::generate_around_path(5, 5, &path, 4).unwrap();
+
+        assert!(is_perfect(&maze));
+        assert_eq!(Some(path.clone()), maze.solve(path[0], path[6]));
+    }
+
+    #[test]
+    fn generate_with_path_shape_zigzag() {
+        let zigzag: Vec<MazeCell> = (0..6)
+            .flat_map(|x| {
+                let (y0, y1) = if x % 2 == 0 { (1, 2) } else { (2, 1) };
+                vec![MazeCell::new(x, y0), MazeCell::new(x, y1)]
+            })
+            .collect();
+
+        for seed in 0..5 {
+            let maze = Maze::generate_with_path_shape(6, 4, &zigzag, seed).unwrap();
+
+            assert_eq!(
+                Some(zigzag.clone()),
+                maze.solve(zigzag[0], zigzag[zigzag.len() - 1])
+            );
+        }
+    }
+
+    #[test]
+    fn generate_around_path_errors() {
+        let (a, b, c) = (
+            MazeCell::new(0, 0),
+            MazeCell::new(1, 0),
+            MazeCell::new(1, 1),
+        );
+
+        assert_eq!(
+            Err(MazeError::NotAdjacent(a, c)),
+            Maze::generate_around_path(3, 3, &[b, a, c], 0)
+        );
+        assert_eq!(
+            Err(MazeError::CellRepeated(a)),
+            Maze::generate_around_path(3, 3, &[a, b, a], 0)
+        );
+        assert_eq!(
+            Err(MazeError::CellOutOfBounds(MazeCell::new(3, 0))),
+            Maze::generate_around_path(3, 3, &[MazeCell::new(3, 0)], 0)
+        );
+    }
+
+    #[test]
+    fn carve_by_order_row_order() {
+        let order: Vec<MazeCell> = MazeIterator::new(&Maze::new(3, 3)).collect();
+
+        let maze = Maze::carve_by_order(3, 3, &order).unwrap();
+
+        // each row after the first joins the cell below it, so the maze is a comb of
+        // columns rising from the bottom row
+        let mut expected = Maze::new(3, 3);
+        let edges: Vec<(MazeCell, MazeCell)> =
+            (0..2)
+                .map(|x| (MazeCell::new(x, 0), MazeCell::new(x + 1, 0)))
+                .chain((0..3).flat_map(|x| {
+                    (0..2).map(move |y| (MazeCell::new(x, y), MazeCell::new(x, y + 1)))
+                }))
+                .collect();
+        expected.carve_edges(&edges).unwrap();
+        assert_eq!(expected, maze);
+    }
+
+    #[test]
+    fn carve_by_order_scattered_order_is_perfect() {
+        let order: Vec<MazeCell> = [(0, 0), (2, 2), (0, 2), (1, 1), (2, 0), (1, 0)]
+            .iter()
+            .chain(&[(0, 1), (1, 2), (2, 1)])
+            .map(|&(x, y)| MazeCell::new(x, y))
+            .collect();
+
+        let maze = Maze::carve_by_order(3, 3, &order).unwrap();
+
+        assert!(is_perfect(&maze));
+        assert_eq!(
+            Err(MazeError::CellNotCovered(MazeCell::new(2, 1))),
+            Maze::carve_by_order(3, 3, &order[..8])
+        );
+    }
+
+    #[test]
+    fn complete_keeps_template_passages() {
+        let mut template = Maze::new(5, 5);
+        template
+            .connect(MazeCell::new(2, 2), MazeCell::new(2, 3))
+            .unwrap();
+
+        for &algorithm in &[Algorithm::BinaryTree, Algorithm::RecursiveBacktracker] {
+            let maze = Maze::complete(template.clone(), algorithm, 6).unwrap();
+
+            assert!(is_perfect(&maze));
+            assert_eq!(
+                Some(MazeCell::new(2, 3)),
+                maze.try_move(MazeCell::new(2, 2), Direction::North)
+            );
+        }
+    }
+
+    #[test]
+    fn complete_template_with_loop() {
+        let mut template = Maze::new(3, 3);
+        template.open_room(Rect::new(0, 0, 2, 2)).unwrap();
+
+        assert_eq!(
+            Err(MazeError::ClosesLoop(
+                MazeCell::new(0, 1),
+                MazeCell::new(1, 1)
+            )),
+            Maze::complete(template, Algorithm::Sidewinder, 0)
+        );
+    }
+
+    #[test]
+    fn open_wall_errors() {
+        let mut maze = Maze::new(3, 3);
+
+        assert_eq!(
+            Err(WallError::AtNorthEdge),
+            maze.open_north_wall(MazeCell::new(1, 2))
+        );
+        assert_eq!(
+            Err(WallError::AtEastEdge),
+            maze.open_east_wall(MazeCell::new(2, 1))
+        );
+        assert_eq!(
+            Err(WallError::OutOfBounds),
+            maze.open_north_wall(MazeCell::new(3, 0))
+        );
+        assert_eq!(Maze::new(3, 3), maze);
+    }
+
+    /// A loop around the left four cells, with a tail running east then north from
+    /// (1, 0)
+    /// ┌───────┬───┐
+    /// │       │   │
+    /// │       ╵   │
+    /// │           │
+    /// └───────────┘
+    fn build_loop_with_tail() -> Maze {
+        let mut maze = Maze::new(3, 2);
+        maze.open_east_wall(MazeCell::new(0, 0)).unwrap();
+        maze.open_east_wall(MazeCell::new(0, 1)).unwrap();
+        maze.open_north_wall(MazeCell::new(0, 0)).unwrap();
+        maze.open_north_wall(MazeCell::new(1, 0)).unwrap();
+        maze.open_east_wall(MazeCell::new(1, 0)).unwrap();
+        maze.open_north_wall(MazeCell::new(2, 0)).unwrap();
+        maze
+    }
+
+    /// A single row of cells joined into one straight corridor
+    fn build_corridor(len: u32) -> Maze {
+        let mut maze = Maze::new(len, 1);
+        let edges: Vec<(MazeCell, MazeCell)> = (0..len - 1)
+            .map(|x| (MazeCell::new(x, 0), MazeCell::new(x + 1, 0)))
+            .collect();
+        maze.carve_edges(&edges).unwrap();
+        maze
+    }
+
+    fn build_2x2() -> Maze {
+        let mut maze = Maze::new(2, 2);
+        maze.open_east_wall(MazeCell::new(0, 0)).unwrap();
+        maze.open_north_wall(MazeCell::new(0, 0)).unwrap();
+        maze.open_east_wall(MazeCell::new(0, 1)).unwrap();
+        maze
+    }
+
+    #[test]
+    fn render_scaled_corridor_1_wall_1() {
+        let maze = build_2x2();
+
+        assert_display_snapshot_matches!(maze.render_scaled(1, 1));
+    }
+
+    #[test]
+    fn render_scaled_corridor_2_wall_1() {
+        let maze = build_2x2();
+
+        assert_display_snapshot_matches!(maze.render_scaled(2, 1));
+    }
+
+    #[test]
+    fn to_block_grid_2x2() {
+        let maze = build_2x2();
+
+        let grid = maze.to_block_grid();
+
+        assert_eq!(5, grid.len());
+        assert!(grid.iter().all(|column| column.len() == 5));
+        for cell in MazeIterator::new(&maze) {
+            assert!(!grid[2 * cell.x as usize + 1][2 * cell.y as usize + 1]);
+        }
+        // the open wall between (0, 0) and (1, 0) and the closed wall between
+        // (1, 0) and (1, 1)
+        assert!(!grid[2][1]);
+        assert!(grid[3][2]);
+    }
+
+    #[test]
+    fn from_block_grid_round_trip() {
+        for maze in &[build_2x2(), Maze::recursive_backtracker_seeded(7, 4, 6)] {
+            assert_eq!(
+                Ok(maze.clone()),
+                Maze::from_block_grid(&maze.to_block_grid())
+            );
+        }
+    }
+
+    #[test]
+    fn from_block_grid_errors() {
+        assert_eq!(
+            Err(MazeError::InvalidBlockGrid(4, 5)),
+            Maze::from_block_grid(&vec![vec![true; 5]; 4])
+        );
+        assert_eq!(
+            Err(MazeError::InvalidBlockGrid(0, 0)),
+            Maze::from_block_grid(&[])
+        );
+
+        let mut ragged = Maze::new(2, 2).to_block_grid();
+        ragged[3].pop();
+        assert_eq!(
+            Err(MazeError::InvalidBlockGrid(5, 4)),
+            Maze::from_block_grid(&ragged)
+        );
+
+        let mut blocked = Maze::new(2, 2).to_block_grid();
+        blocked[3][1] = true;
+        assert_eq!(
+            Err(MazeError::CellCenterBlocked(MazeCell::new(1, 0))),
+            Maze::from_block_grid(&blocked)
+        );
+    }
+
+    #[test]
+    fn centermost_reachable_all_open() {
+        let mut maze = Maze::new(3, 3);
+        for wall in maze.walls.iter_mut() {
+            *wall = Wall::Open;
+        }
+
+        assert_eq!(
+            MazeCell::new(1, 1),
+            maze.centermost_reachable(MazeCell::new(0, 0))
+        );
+    }
+
+    #[test]
+    fn centermost_reachable_isolated_center() {
+        let mut maze = Maze::new(3, 3);
+        maze.open_east_wall(MazeCell::new(0, 0)).unwrap();
+        maze.open_north_wall(MazeCell::new(0, 0)).unwrap();
+
+        assert_eq!(
+            MazeCell::new(1, 0),
+            maze.centermost_reachable(MazeCell::new(0, 0))
+        );
+    }
+
+    #[test]
+    fn write_to_matches_display() {
+        let maze = build_sidewinder_alternating_bool_1usize();
+        let mut rendered = String::new();
+
+        maze.write_to(&mut rendered).unwrap();
+
+        assert_eq!(format!("{}", maze), rendered);
+    }
+
+    #[test]
+    fn cell_at_char_2x2() {
+        // ┌───────┐
+        // │       │
+        // │   ╶───┤
+        // │       │
+        // └───────┘
+        let maze = build_2x2();
+
+        assert_eq!(Some(MazeCell::new(0, 1)), maze.cell_at_char(1, 1));
+        assert_eq!(Some(MazeCell::new(1, 1)), maze.cell_at_char(7, 1));
+        assert_eq!(Some(MazeCell::new(0, 0)), maze.cell_at_char(2, 3));
+        assert_eq!(Some(MazeCell::new(1, 0)), maze.cell_at_char(5, 3));
+        assert_eq!(None, maze.cell_at_char(4, 3));
+        assert_eq!(None, maze.cell_at_char(2, 2));
+        assert_eq!(None, maze.cell_at_char(9, 1));
+        assert_eq!(None, maze.cell_at_char(1, 5));
+    }
+
+    #[test]
+    fn cell_from_pixel() {
+        let maze = Maze::new(3, 2);
+
+        assert_eq!(
+            Some(MazeCell::new(0, 1)),
+            maze.cell_from_pixel(5.0, 5.0, 10.0)
+        );
+        assert_eq!(
+            Some(MazeCell::new(2, 0)),
+            maze.cell_from_pixel(25.0, 15.0, 10.0)
+        );
+        assert_eq!(
+            Some(MazeCell::new(1, 0)),
+            maze.cell_from_pixel(10.0, 10.0, 10.0)
+        );
+        assert_eq!(None, maze.cell_from_pixel(30.0, 5.0, 10.0));
+        assert_eq!(None, maze.cell_from_pixel(5.0, 20.0, 10.0));
+        assert_eq!(None, maze.cell_from_pixel(-0.5, 5.0, 10.0));
+        assert_eq!(None, maze.cell_from_pixel(5.0, 5.0, 0.0));
+    }
+
+    #[test]
+    fn render_lines_join_to_display() {
+        let maze = build_sidewinder_alternating_bool_1usize();
+
+        let lines = maze.render_lines();
+
+        assert_eq!(7, lines.len());
+        assert_eq!(maze.to_string(), lines.join(LINE_ENDING));
+    }
+
+    #[test]
+    fn print_to_matches_display() {
+        let maze = build_sidewinder_alternating_bool_1usize();
+        let mut printed = vec![];
+
+        maze.print_to(&mut printed).unwrap();
+
+        assert_eq!(
+            format!("{}{}", maze, LINE_ENDING),
+            String::from_utf8(printed).unwrap()
+        );
+    }
+
+    #[test]
+    fn from_ascii() {
+        let maze = Maze::from_ascii(
+            "+---+---+\n\
+             |       |\n\
+             +   +---+\n\
+             |       |\n\
+             +---+---+",
+        )
+        .unwrap();
+
+        assert_eq!(build_2x2(), maze);
+    }
+
+    #[test]
+    fn to_ascii_round_trips() {
+        let maze = Maze::recursive_backtracker_seeded(5, 4, 6);
+
+        assert_eq!(Ok(maze.clone()), Maze::from_ascii(&maze.to_ascii()));
+    }
+
+    #[test]
+    fn to_rust_literal_2x2() {
+        let maze = build_2x2();
+
+        // the expected literal is pasted below to check that it rebuilds the maze
+        let expected = [
+            "Maze::from_ascii(concat!(",
+            "    \"+---+---+\\n\",",
+            "    \"|       |\\n\",",
+            "    \"+   +---+\\n\",",
+            "    \"|       |\\n\",",
+            "    \"+---+---+\",",
+            "))",
+            ".unwrap()",
+        ];
+        assert_eq!(expected.join(LINE_ENDING), maze.to_rust_literal());
+
+        let rebuilt = Maze::from_ascii(concat!(
+            "+---+---+\n",
+            "|       |\n",
+            "+   +---+\n",
+            "|       |\n",
+            "+---+---+",
+        ))
+        .unwrap();
+        assert_eq!(maze, rebuilt);
+    }
+
+    #[test]
+    fn from_ascii_errors() {
+        assert_eq!(Err(ParseError::TooSmall), Maze::from_ascii("+---+"));
+        assert_eq!(
+            Err(ParseError::RaggedLine(1)),
+            Maze::from_ascii("+---+\n|  |\n+---+")
+        );
+        assert_eq!(
+            Err(ParseError::UnexpectedChar('x', 1, 4)),
+            Maze::from_ascii("+---+---+\n|   x   |\n+---+---+")
+        );
+    }
+
+    #[test]
+    fn junctions_binary_tree_alternating_bool() {
+        let mut val = false;
+        let mock_rand_bool = || {
+            val = !val;
+            val
+        };
+        let maze = Maze::binary_tree_with_rng(3, 3, &mut MockRng::new(mock_rand_bool, || 0));
+
+        // ┌───────────┐
+        // │           │
+        // ├───╴   ╷   │
+        // │       │   │
+        // │   ┌───┘   │
+        // │   │       │
+        // └───┴───────┘
+        assert_eq!(vec![MazeCell::new(1, 2)], maze.junctions());
+    }
+
+    #[test]
+    fn corridor_depth_is_highest_mid_corridor() {
+        let maze = build_corridor(7);
+
+        let depths: Vec<u32> = maze
+            .corridor_depth()
+            .into_iter()
+            .map(|column| column[0])
+            .collect();
+
+        assert_eq!(vec![0, 1, 2, 3, 2, 1, 0], depths);
+    }
+
+    #[test]
+    fn trap_score_dead_end_below_junction() {
+        // ┌───────────┐
+        // │           │
+        // ├───╴   ╷   │
+        // │       │   │
+        // │   ┌───┘   │
+        // │   │       │
+        // └───┴───────┘
+        let maze = build_sidewinder_alternating_bool_1usize();
+        let junction = MazeCell::new(1, 2);
+
+        assert_eq!(vec![junction], maze.junctions());
+        assert_eq!(9, maze.trap_score(junction));
+        assert_eq!(6, maze.trap_score(MazeCell::new(0, 0)));
+        assert_eq!(5, maze.trap_score(MazeCell::new(1, 0)));
+        assert_eq!(0, build_2x2().trap_score(MazeCell::new(0, 0)));
+    }
+
+    #[test]
+    fn fill_dead_ends_leaves_solution() {
+        let mut maze = Maze::recursive_backtracker_seeded(6, 6, 11);
+        let start = MazeCell::new(0, 0);
+        let end = MazeCell::new(5, 5);
+        let solution = maze.solve(start, end).unwrap();
+
+        maze.fill_dead_ends(start, end);
+
+        let open_cells: Vec<MazeCell> = MazeIterator::new(&maze)
+            .filter(|&cell| maze.open_wall_count(cell) > 0)
+            .collect();
+        assert_eq!(solution.len(), open_cells.len());
+        assert!(open_cells.iter().all(|cell| solution.contains(cell)));
+        assert_eq!(Some(solution), maze.solve(start, end));
+    }
+
+    #[test]
+    fn quadrant_open_counts_binary_tree_all_true() {
+        let maze = Maze::binary_tree_with_rng(4, 4, &mut MockRng::new(|| true, || 0));
+
+        // every cell opens north apart from the top row, which opens east instead, so
+        // the north east quadrant loses the passage the corner cell can't carve
+        assert_eq!([4, 4, 4, 3], maze.quadrant_open_counts());
+    }
+
+    #[test]
+    fn passage_bias_binary_tree_all_true() {
+        let maze = Maze::binary_tree_with_rng(3, 3, &mut MockRng::new(|| true, || 0));
+
+        assert_eq!((6, 2), maze.passage_bias());
+    }
+
+    #[test]
+    fn loop_count_after_braiding() {
+        let mut maze = Maze::recursive_backtracker_seeded(6, 6, 10);
+        assert_eq!(0, maze.loop_count());
+
+        let closed_walls: Vec<usize> = (0..maze.walls.len())
+            .filter(|&index| maze.walls[index] == Wall::Closed)
+            .take(2)
+            .collect();
+        for index in closed_walls {
+            maze.walls[index] = Wall::Open;
+        }
+
+        assert_eq!(2, maze.loop_count());
+    }
+
+    #[test]
+    fn min_walls_to_connect_three_components() {
+        // (0, 0) and (1, 0) are joined, and (0, 1) and (1, 1) are each on their own
+        let mut maze = Maze::new(2, 2);
+        maze.connect(MazeCell::new(0, 0), MazeCell::new(1, 0))
+            .unwrap();
+
+        assert_eq!(2, maze.min_walls_to_connect());
+        assert_eq!(0, build_2x2().min_walls_to_connect());
+    }
+
+    #[test]
+    fn river_factor_straight_corridor() {
+        let maze = build_corridor(5);
+
+        assert_eq!(1.0, maze.river_factor());
+    }
+
+    #[test]
+    fn estimated_cover_time_is_reproducible() {
+        let maze = Maze::recursive_backtracker_seeded(5, 5, 3);
+
+        assert_eq!(
+            maze.estimated_cover_time(20, 7),
+            maze.estimated_cover_time(20, 7)
+        );
+        assert_eq!(Some(0.0), Maze::new(1, 1).estimated_cover_time(3, 7));
+    }
+
+    #[test]
+    fn estimated_cover_time_without_samples() {
+        assert_eq!(None, build_corridor(5).estimated_cover_time(0, 1));
+    }
+
+    #[test]
+    fn estimated_cover_time_straight_corridor() {
+        let maze = build_corridor(5);
+
+        // a walk from one end of a corridor takes (length - 1)^2 steps on average to
+        // reach the other end
+        let estimate = maze.estimated_cover_time(2000, 1).unwrap();
+        assert!((estimate - 16.0).abs() < 1.0, "{}", estimate);
+    }
+
+    #[test]
+    fn river_factor_winding_corridor() {
+        // both corridor cells of the 2x2 maze turn a corner
+        assert_eq!(0.0, build_2x2().river_factor());
+    }
+
+    #[test]
+    fn regenerate_region_leaves_outside_unchanged() {
+        let original = Maze::recursive_backtracker_seeded(6, 6, 5);
+        let mut maze = Maze::recursive_backtracker_seeded(6, 6, 5);
+        let rect = Rect::new(1, 2, 3, 3);
+
+        maze.regenerate_region(rect, Algorithm::Sidewinder, 9)
+            .unwrap();
+
+        let start = MazeCell::new(0, 0);
+        let path = MazePath::new(&maze, start);
+        for cell in MazeIterator::new(&maze) {
+            assert!(path.distance_to(cell).is_some());
+            if rect.contains(cell) {
+                continue;
+            }
+            for neighbor in maze.neighbors_of(cell) {
+                if !rect.contains(neighbor) {
+                    let index = maze.wall_index_between(cell, neighbor).unwrap();
+                    assert_eq!(original.walls[index], maze.walls[index]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn regenerate_region_reconnects_isolated_region() {
+        let mut maze = Maze::new(4, 4);
+        let rect = Rect::new(0, 0, 2, 2);
+
+        maze.regenerate_region(rect, Algorithm::BinaryTree, 1)
+            .unwrap();
+
+        let path = MazePath::new(&maze, MazeCell::new(0, 0));
+        let reachable = MazeIterator::new(&maze)
+            .filter(|&cell| path.distance_to(cell).is_some())
+            .count();
+        assert_eq!(5, reachable);
+    }
+
+    #[test]
+    fn regenerate_region_out_of_bounds() {
+        let mut maze = Maze::new(4, 4);
+        let rect = Rect::new(3, 3, 2, 1);
+
+        assert_eq!(
+            Err(MazeError::RectOutOfBounds(rect)),
+            maze.regenerate_region(rect, Algorithm::BinaryTree, 1)
+        );
+    }
+
+    #[test]
+    fn bfs_order_distances_increase() {
+        let maze = Maze::recursive_backtracker_seeded(5, 5, 2);
+        let start = MazeCell::new(2, 2);
+
+        let order: Vec<(MazeCell, u32)> = maze.bfs_order(start).collect();
+
+        assert_eq!((start, 0), order[0]);
+        assert_eq!(25, order.len());
+        assert!(order.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+        let path = MazePath::new(&maze, start);
+        assert!(order
+            .iter()
+            .all(|&(cell, distance)| path.distance_to(cell) == Some(distance)));
+    }
+
+    #[test]
+    fn cells_at_distance() {
+        let maze = build_2x2();
+        let start = MazeCell::new(0, 0);
+
+        assert_eq!(vec![start], maze.cells_at_distance(start, 0));
+        assert_eq!(
+            vec![MazeCell::new(1, 0), MazeCell::new(0, 1)],
+            maze.cells_at_distance(start, 1)
+        );
+        assert_eq!(vec![MazeCell::new(1, 1)], maze.cells_at_distance(start, 2));
+        assert!(maze.cells_at_distance(start, 3).is_empty());
+    }
+
+    #[test]
+    fn farthest_deadend_pair() {
+        // ┌───────────┐
+        // │           │
+        // ├───╴   ╷   │
+        // │       │   │
+        // │   ┌───┘   │
+        // │   │       │
+        // └───┴───────┘
+        let maze = build_sidewinder_alternating_bool_1usize();
+
+        assert_eq!(
+            vec![
+                MazeCell::new(0, 0),
+                MazeCell::new(1, 0),
+                MazeCell::new(0, 2)
+            ],
+            maze.dead_ends()
+        );
+        assert_eq!(
+            Some((MazeCell::new(0, 0), MazeCell::new(1, 0))),
+            maze.farthest_deadend_pair()
+        );
+        assert_eq!(
+            None,
+            build_2x2()
+                .crop(Rect::new(0, 0, 1, 1))
+                .unwrap()
+                .farthest_deadend_pair()
+        );
+    }
+
+    #[test]
+    fn deepest_offpath_deadend() {
+        let mut maze = Maze::new(3, 3);
+        maze.carve_edges(&[
+            (MazeCell::new(0, 0), MazeCell::new(1, 0)),
+            (MazeCell::new(1, 0), MazeCell::new(2, 0)),
+            (MazeCell::new(1, 0), MazeCell::new(1, 1)),
+            (MazeCell::new(1, 1), MazeCell::new(1, 2)),
+            (MazeCell::new(1, 2), MazeCell::new(0, 2)),
+            (MazeCell::new(0, 2), MazeCell::new(0, 1)),
+            (MazeCell::new(1, 1), MazeCell::new(2, 1)),
+            (MazeCell::new(2, 1), MazeCell::new(2, 2)),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            vec![
+                MazeCell::new(0, 0),
+                MazeCell::new(2, 0),
+                MazeCell::new(0, 1),
+                MazeCell::new(2, 2),
+            ],
+            maze.dead_ends()
+        );
+        assert_eq!(
+            Some(MazeCell::new(0, 1)),
+            maze.deepest_offpath_deadend(MazeCell::new(0, 0), MazeCell::new(2, 0))
+        );
+    }
+
+    #[test]
+    fn distance_to_solution() {
+        // ┌───────┬───┐
+        // │       │   │
+        // │   ╷   ╵   │
+        // │   │       │
+        // ├───┘   ╶───┤
+        // │           │
+        // └───────────┘
+        let mut maze = Maze::new(3, 3);
+        maze.carve_edges(&[
+            (MazeCell::new(0, 0), MazeCell::new(1, 0)),
+            (MazeCell::new(1, 0), MazeCell::new(2, 0)),
+            (MazeCell::new(1, 0), MazeCell::new(1, 1)),
+            (MazeCell::new(1, 1), MazeCell::new(1, 2)),
+            (MazeCell::new(1, 2), MazeCell::new(0, 2)),
+            (MazeCell::new(0, 2), MazeCell::new(0, 1)),
+            (MazeCell::new(1, 1), MazeCell::new(2, 1)),
+            (MazeCell::new(2, 1), MazeCell::new(2, 2)),
+        ])
+        .unwrap();
+
+        // the solution runs along the bottom row, and the spur ending at (0, 1) is the
+        // deepest
+        assert_eq!(
+            vec![vec![0, 4, 3], vec![0, 1, 2], vec![0, 2, 3]],
+            maze.distance_to_solution(MazeCell::new(0, 0), MazeCell::new(2, 0))
+        );
+        assert_eq!(
+            vec![vec![u32::MAX]; 2],
+            Maze::new(2, 1).distance_to_solution(MazeCell::new(0, 0), MazeCell::new(1, 0))
+        );
+    }
+
+    #[test]
+    fn render_solution_only() {
+        let maze = Maze::recursive_backtracker_seeded(4, 4, 2);
+        let (start, end) = (MazeCell::new(0, 0), MazeCell::new(3, 3));
+
+        assert_display_snapshot_matches!(format!(
+            "{}{}{}",
+            maze,
+            LINE_ENDING,
+            maze.render_solution_only(start, end)
+        ));
+    }
+
+    #[test]
+    fn render_with_endpoints() {
+        let maze = build_sidewinder_alternating_bool_1usize();
+
+        assert_display_snapshot_matches!(
+            maze.render_with_endpoints(MazeCell::new(0, 0), MazeCell::new(2, 2))
+        );
+    }
+
+    #[test]
+    fn render_search_frame() {
+        let maze = build_2x2();
+        let visited = [MazeCell::new(0, 0)];
+        let frontier = [MazeCell::new(0, 1)];
+
+        let frame = maze.render_search_frame(&visited, &frontier, MazeCell::new(1, 0));
+
+        assert_display_snapshot_matches!(frame);
+    }
+
+    #[test]
+    fn render_with_labels_diagonal() {
+        let maze = build_sidewinder_alternating_bool_1usize();
+
+        let rendered = maze.render_with_labels(|cell| {
+            if cell.x == cell.y {
+                std::char::from_digit(cell.x, 10)
+            } else {
+                None
+            }
+        });
+
+        assert_display_snapshot_matches!(rendered);
+    }
+
+    #[test]
+    fn render_with_endpoint_markers_same_cell() {
+        let maze = Maze::new(1, 1);
+        let markers = EndpointMarkers {
+            start: 'A',
+            end: 'B',
+            both: '@',
+        };
+
+        assert_eq!(
+            "┌───┐\n│ @ │\n└───┘",
+            maze.render_with_endpoint_markers(MazeCell::new(0, 0), MazeCell::new(0, 0), &markers)
+        );
+    }
+
+    #[test]
+    fn cells_for_wall_matches_wall_indexes() {
+        let maze = Maze::new(4, 3);
+        for cell in MazeIterator::new(&maze) {
+            if let Some(index) = maze.north_wall_index_for_cell(cell.x, cell.y) {
+                assert_eq!(
+                    (cell, MazeCell::new(cell.x, cell.y + 1)),
+                    maze.cells_for_wall(index)
+                );
+            }
+            if let Some(index) = maze.east_wall_index_for_cell(cell.x, cell.y) {
+                assert_eq!(
+                    (cell, MazeCell::new(cell.x + 1, cell.y)),
+                    maze.cells_for_wall(index)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn perturb_keeps_maze_perfect() {
+        let original = Maze::recursive_backtracker_seeded(6, 6, 4);
+        let mut maze = original.clone();
+
+        maze.perturb(5, 8);
+
+        assert!(is_perfect(&maze));
+        assert_ne!(original, maze);
+    }
+
+    #[test]
+    fn distance_matrix_symmetric() {
+        let maze = Maze::recursive_backtracker_seeded(4, 3, 6);
+
+        let matrix = maze.distance_matrix();
+
+        assert_eq!(12, matrix.len());
+        for (i, row) in matrix.iter().enumerate() {
+            assert_eq!(0, row[i]);
+            for (j, &distance) in row.iter().enumerate() {
+                assert_eq!(distance, matrix[j][i]);
+            }
+        }
+        let solution = maze
+            .solve(MazeCell::new(0, 0), MazeCell::new(3, 2))
+            .unwrap();
+        assert_eq!(solution.len() as u32 - 1, matrix[0][3 + 2 * 4]);
+    }
+
+    #[test]
+    fn adjacency_list_2x2() {
+        let maze = build_2x2();
+
+        let adjacency = maze.adjacency_list();
+
+        // (0, 0) opens north to (0, 1) at index 2 and east to (1, 0) at index 1
+        assert_eq!(vec![2, 1], adjacency[0]);
+        assert_eq!(vec![vec![2, 1], vec![0], vec![3, 0], vec![2]], adjacency);
+    }
+
+    #[test]
+    fn distance_matrix_unreachable() {
+        let maze = Maze::new(2, 1);
+
+        assert_eq!(
+            vec![vec![0, u32::MAX], vec![u32::MAX, 0]],
+            maze.distance_matrix()
+        );
+    }
+
+    #[test]
+    fn hardest_start_for_is_furthest_from_exit() {
+        let maze = Maze::recursive_backtracker_seeded(8, 6, 5);
+        let exit = MazeCell::new(7, 5);
+
+        let start = maze.hardest_start_for(exit);
+
+        let distances = &maze.distance_matrix()[maze.cell_index(exit)];
+        let max_distance = distances.iter().max().unwrap();
+        assert_eq!(
+            Some(*max_distance),
+            maze.solve(start, exit).map(|path| path.len() as u32 - 1)
+        );
+    }
+
+    #[test]
+    fn graph_center_of_corridor() {
+        // ┌───────────────┐
+        // │               │
+        // ├───────────╴   │
+        // │               │
+        // └───────────────┘
+        let mut maze = Maze::new(4, 2);
+        maze.carve_edges(&[
+            (MazeCell::new(0, 0), MazeCell::new(1, 0)),
+            (MazeCell::new(1, 0), MazeCell::new(2, 0)),
+            (MazeCell::new(2, 0), MazeCell::new(3, 0)),
+            (MazeCell::new(3, 0), MazeCell::new(3, 1)),
+            (MazeCell::new(3, 1), MazeCell::new(2, 1)),
+            (MazeCell::new(2, 1), MazeCell::new(1, 1)),
+            (MazeCell::new(1, 1), MazeCell::new(0, 1)),
+        ])
+        .unwrap();
+
+        assert_eq!(7, maze.eccentricity(MazeCell::new(0, 0)));
+        assert_eq!(4, maze.eccentricity(MazeCell::new(3, 0)));
+        assert_eq!(
+            vec![MazeCell::new(3, 0), MazeCell::new(3, 1)],
+            maze.graph_center()
+        );
+    }
+
+    #[test]
+    fn graph_center_of_odd_corridor() {
+        let maze = build_corridor(5);
+
+        assert_eq!(vec![MazeCell::new(2, 0)], maze.graph_center());
+    }
+
+    #[test]
+    fn generate_until_never_satisfied() {
+        let tries = std::cell::Cell::new(0);
+        let maze = Maze::generate_until(
+            4,
+            4,
+            Algorithm::RecursiveBacktracker,
+            0,
+            |_maze| {
+                tries.set(tries.get() + 1);
+                false
+            },
+            5,
+        );
+
+        assert_eq!(None, maze);
+        assert_eq!(5, tries.get());
+    }
+
+    #[test]
+    fn generate_until_long_solution() {
+        let start = MazeCell::new(0, 0);
+        let end = MazeCell::new(5, 5);
+        let long_solution =
+            |maze: &Maze| maze.solve(start, end).is_some_and(|path| path.len() > 20);
+
+        let maze =
+            Maze::generate_until(6, 6, Algorithm::RecursiveBacktracker, 0, long_solution, 100)
+                .unwrap();
+
+        assert!(long_solution(&maze));
+    }
+
+    #[test]
+    fn generate_batch_is_distinct() {
+        let mazes = Maze::generate_batch(6, 6, Algorithm::Sidewinder, 10, 100);
+
+        assert_eq!(10, mazes.len());
+        for (i, maze) in mazes.iter().enumerate() {
+            assert!(mazes[i + 1..].iter().all(|other| other != maze));
+        }
+    }
+
+    #[test]
+    fn open_probability_grid_binary_tree_top_row() {
+        let probabilities = Maze::open_probability_grid(50, 4, 4, Algorithm::BinaryTree, 0);
+
+        let maze = Maze::new(4, 4);
+        assert_eq!(maze.walls.len(), probabilities.len());
+        for x in 0..3 {
+            let index = maze.east_wall_index_for_cell(x, 3).unwrap();
+            assert_eq!(1.0, probabilities[index]);
+        }
+        let index = maze.east_wall_index_for_cell(0, 0).unwrap();
+        assert!(probabilities[index] > 0.2 && probabilities[index] < 0.8);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use insta::assert_display_snapshot_matches;
+    #[test]
+    fn open_probability_grid_without_samples() {
+        assert_eq!(
+            vec![0.0; 4],
+            Maze::open_probability_grid(0, 2, 2, Algorithm::BinaryTree, 0)
+        );
+    }
 
     #[test]
-    fn new_3x3() {
-        let maze = Maze::new(3, 3);
+    fn generate_min_difficulty_meets_minimum() {
+        let maze = Maze::generate_min_difficulty(6, 6, Algorithm::BinaryTree, 16, 3);
 
-        assert_display_snapshot_matches!(maze);
+        let diameter = maze.distance_matrix().into_iter().flatten().max().unwrap();
+        assert!(diameter >= 16);
     }
 
     #[test]
-    fn wall_indexes_for_cell_00() {
-        let maze = Maze::new(3, 3);
-        let x = 0;
-        let y = 0;
+    fn generate_with_deadend_ratio_near_target() {
+        let maze = Maze::generate_with_deadend_ratio(10, 10, 0.05, 7);
 
-        assert_eq!(Some(0), maze.north_wall_index_for_cell(x, y));
-        assert_eq!(Some(6), maze.east_wall_index_for_cell(x, y));
-        assert_eq!(None, maze.south_wall_index_for_cell(x, y));
-        assert_eq!(None, maze.west_wall_index_for_cell(x, y));
+        let ratio = maze.dead_ends().len() as f64 / 100.0;
+        assert!(ratio <= 0.05);
+        assert!(ratio >= 0.03);
     }
 
     #[test]
-    fn wall_indexes_for_cell_11() {
-        let maze = Maze::new(3, 3);
-        let x = 1;
-        let y = 1;
+    fn generate_with_deadend_ratio_out_of_range_target() {
+        for &target in &[-1.0, f64::NAN] {
+            let maze = Maze::generate_with_deadend_ratio(6, 6, target, 2);
 
-        assert_eq!(Some(4), maze.north_wall_index_for_cell(x, y));
-        assert_eq!(Some(10), maze.east_wall_index_for_cell(x, y));
-        assert_eq!(Some(1), maze.south_wall_index_for_cell(x, y));
-        assert_eq!(Some(7), maze.west_wall_index_for_cell(x, y));
+            assert!(maze.dead_ends().is_empty());
+        }
+        assert_eq!(
+            Maze::recursive_backtracker_seeded(6, 6, 2),
+            Maze::generate_with_deadend_ratio(6, 6, 2.0, 2)
+        );
     }
 
     #[test]
-    fn wall_indexes_for_cell_22() {
-        let maze = Maze::new(3, 3);
-        let x = 2;
-        let y = 2;
+    fn try_move_from_interior_cell() {
+        let maze = build_sidewinder_alternating_bool_1usize();
+        let cell = MazeCell::new(1, 1);
 
-        assert_eq!(None, maze.north_wall_index_for_cell(x, y));
-        assert_eq!(None, maze.east_wall_index_for_cell(x, y));
-        assert_eq!(Some(5), maze.south_wall_index_for_cell(x, y));
-        assert_eq!(Some(11), maze.west_wall_index_for_cell(x, y));
+        assert_eq!(
+            Some(MazeCell::new(1, 2)),
+            maze.try_move(cell, Direction::North)
+        );
+        assert_eq!(None, maze.try_move(cell, Direction::East));
+        assert_eq!(None, maze.try_move(cell, Direction::South));
+        assert_eq!(
+            Some(MazeCell::new(0, 1)),
+            maze.try_move(cell, Direction::West)
+        );
     }
 
     #[test]
-    fn open_north_wall() {
-        let mut maze = Maze::new(3, 3);
-        let mut maze_iter = MazeIterator::new(&maze);
-        let cell = maze_iter.next().unwrap();
+    fn render_with_style_rounded() {
+        let maze = build_sidewinder_alternating_bool_1usize();
 
-        maze.open_north_wall(cell).unwrap();
+        assert_display_snapshot_matches!(maze.render_with_style(RenderStyle::Rounded));
+    }
 
-        assert_display_snapshot_matches!(maze);
+    #[test]
+    fn render_with_style_sharp_matches_display() {
+        let maze = build_sidewinder_alternating_bool_1usize();
+
+        assert_eq!(maze.to_string(), maze.render_with_style(RenderStyle::Sharp));
     }
 
     #[test]
-    fn open_east_wall() {
-        let mut maze = Maze::new(3, 3);
-        let mut maze_iter = MazeIterator::new(&maze);
-        let cell = maze_iter.next().unwrap();
+    fn to_geojson_single_passage() {
+        let mut maze = Maze::new(2, 2);
+        maze.open_east_wall(MazeCell::new(0, 1)).unwrap();
 
-        maze.open_east_wall(cell).unwrap();
+        assert_display_snapshot_matches!(maze.to_geojson(10.0));
+    }
 
-        assert_display_snapshot_matches!(maze);
+    #[test]
+    fn open_room_2x2() {
+        let mut maze = Maze::new(4, 4);
+        let rect = Rect::new(1, 1, 2, 2);
+
+        maze.open_room(rect).unwrap();
+
+        for cell in MazeIterator::new(&maze) {
+            for neighbor in maze.neighbors_of(cell) {
+                let index = maze.wall_index_between(cell, neighbor).unwrap();
+                let expected = if rect.contains(cell) && rect.contains(neighbor) {
+                    Wall::Open
+                } else {
+                    Wall::Closed
+                };
+                assert_eq!(expected, maze.walls[index]);
+            }
+        }
     }
 
     #[test]
-    fn next_cell_open_north_wall() {
+    fn chambers_finds_room_and_ignores_corridors() {
+        let mut maze = Maze::new(5, 5);
+        let room = Rect::new(1, 1, 2, 2);
+        maze.open_room(room).unwrap();
+        let mut corridors: Vec<(MazeCell, MazeCell)> = (0..4)
+            .map(|x| (MazeCell::new(x, 4), MazeCell::new(x + 1, 4)))
+            .collect();
+        corridors.extend((0..4).map(|y| (MazeCell::new(4, y), MazeCell::new(4, y + 1))));
+        corridors.push((MazeCell::new(2, 2), MazeCell::new(2, 3)));
+        maze.carve_edges(&corridors).unwrap();
+
+        assert_eq!(vec![room], maze.chambers(4));
+        assert!(maze.chambers(5).is_empty());
+    }
+
+    #[test]
+    fn chambers_overlapping() {
+        // an L shaped open area holds two overlapping maximal rectangles
         let mut maze = Maze::new(3, 3);
-        let mut maze_iter = MazeIterator::new(&maze);
-        maze_iter.next().unwrap(); // skip the first cell
-        let cell = maze_iter.next().unwrap();
+        maze.open_room(Rect::new(0, 0, 3, 2)).unwrap();
+        maze.open_room(Rect::new(0, 0, 2, 3)).unwrap();
 
-        maze.open_north_wall(cell).unwrap();
+        assert_eq!(
+            vec![Rect::new(0, 0, 2, 3), Rect::new(0, 0, 3, 2)],
+            maze.chambers(1)
+        );
+    }
 
-        assert_display_snapshot_matches!(maze);
+    #[test]
+    fn crop_keeps_internal_walls() {
+        let maze = Maze::recursive_backtracker_seeded(4, 4, 5);
+
+        let cropped = maze.crop(Rect::new(0, 0, 2, 2)).unwrap();
+
+        assert_eq!((2, 2), (cropped.width, cropped.height));
+        for cell in MazeIterator::new(&cropped) {
+            for neighbor in cropped.neighbors_of(cell) {
+                assert_eq!(
+                    maze.is_wall_open(maze.wall_index_between(cell, neighbor)),
+                    cropped.is_wall_open(cropped.wall_index_between(cell, neighbor))
+                );
+            }
+        }
+        assert_eq!(
+            Err(MazeError::RectOutOfBounds(Rect::new(3, 0, 2, 2))),
+            maze.crop(Rect::new(3, 0, 2, 2))
+        );
     }
 
     #[test]
-    fn next_cell_open_east_wall() {
+    fn open_room_out_of_bounds() {
+        let mut maze = Maze::new(4, 4);
+        let rect = Rect::new(3, 0, 2, 2);
+
+        assert_eq!(Err(MazeError::RectOutOfBounds(rect)), maze.open_room(rect));
+    }
+
+    #[test]
+    fn rect_far_outside_maze_is_out_of_bounds() {
+        let mut maze = Maze::new(4, 4);
+
+        let rect = Rect::new(u32::MAX, 0, 2, 1);
+        assert_eq!(Err(MazeError::RectOutOfBounds(rect)), maze.crop(rect));
+        let rect = Rect::new(1, u32::MAX, 1, 2);
+        assert_eq!(Err(MazeError::RectOutOfBounds(rect)), maze.open_room(rect));
+        let rect = Rect::new(u32::MAX, u32::MAX, u32::MAX, u32::MAX);
+        assert_eq!(
+            Err(MazeError::RectOutOfBounds(rect)),
+            maze.regenerate_region(rect, Algorithm::BinaryTree, 1)
+        );
+    }
+
+    #[test]
+    fn rect_overlaps_near_u32_max() {
+        let edge = Rect::new(u32::MAX - 1, 0, 2, 1);
+
+        assert!(edge.overlaps(&Rect::new(u32::MAX - 1, 0, u32::MAX, 1)));
+        assert!(!edge.overlaps(&Rect::new(0, 0, u32::MAX - 1, 1)));
+        assert!(edge.contains(MazeCell::new(u32::MAX, 0)));
+    }
+
+    #[test]
+    fn solution_count_perfect_maze() {
+        let maze = Maze::recursive_backtracker_seeded(5, 5, 1);
+
+        assert_eq!(
+            1,
+            maze.solution_count(MazeCell::new(0, 0), MazeCell::new(4, 4))
+        );
+    }
+
+    #[test]
+    fn solution_count_two_routes() {
         let mut maze = Maze::new(3, 3);
-        let mut maze_iter = MazeIterator::new(&maze);
-        maze_iter.next().unwrap(); // skip the first cell
-        let cell = maze_iter.next().unwrap();
+        maze.open_room(Rect::new(0, 0, 2, 2)).unwrap();
 
-        maze.open_east_wall(cell).unwrap();
+        assert_eq!(
+            2,
+            maze.solution_count(MazeCell::new(0, 0), MazeCell::new(1, 1))
+        );
+        assert_eq!(
+            0,
+            maze.solution_count(MazeCell::new(0, 0), MazeCell::new(2, 2))
+        );
+    }
 
-        assert_display_snapshot_matches!(maze);
+    #[test]
+    fn transform_rotate_90() {
+        let maze = build_sidewinder_alternating_bool_1usize();
+
+        assert_display_snapshot_matches!(maze.transform(Symmetry::Rotate90));
     }
 
     #[test]
-    fn move_to_next_cell_wraps() {
-        let maze = Maze::new(3, 3);
-        let mut maze_iter = MazeIterator::new(&maze);
-        maze_iter.next().unwrap(); // Position: 0, 0
-        maze_iter.next().unwrap(); // Position: 1, 0
-        maze_iter.next().unwrap(); // Position: 2, 0
-        let cell = maze_iter.next().unwrap(); // Position: 0, 1
+    fn transforms_compose_to_identity() {
+        let maze = Maze::recursive_backtracker_seeded(5, 3, 1);
+        let rotated = maze
+            .transform(Symmetry::Rotate90)
+            .transform(Symmetry::Rotate90)
+            .transform(Symmetry::Rotate180);
 
-        assert_eq!(0, cell.x);
-        assert_eq!(1, cell.y);
+        assert_eq!(maze, rotated);
+        for &symmetry in Symmetry::ALL.iter() {
+            let inverse = match symmetry {
+                Symmetry::Rotate90 => Symmetry::Rotate270,
+                Symmetry::Rotate270 => Symmetry::Rotate90,
+                symmetry => symmetry,
+            };
+            assert_eq!(maze, maze.transform(symmetry).transform(inverse));
+        }
     }
 
     #[test]
-    fn move_to_next_cell_returns_none() {
-        let maze = Maze::new(3, 3);
-        let mut maze_iter = MazeIterator::new(&maze);
-        maze_iter.next().unwrap(); // Position: 0, 0
-        maze_iter.next().unwrap(); // Position: 1, 0
-        maze_iter.next().unwrap(); // Position: 2, 0
-        maze_iter.next().unwrap(); // Position: 0, 1
-        maze_iter.next().unwrap(); // Position: 1, 1
-        maze_iter.next().unwrap(); // Position: 2, 1
-        maze_iter.next().unwrap(); // Position: 0, 2
-        maze_iter.next().unwrap(); // Position: 1, 2
-        maze_iter.next().unwrap(); // Position: 2, 2
-        let cell = maze_iter.next();
+    fn to_unicursal_is_a_single_path() {
+        let maze = Maze::recursive_backtracker_seeded(5, 4, 8);
 
-        assert!(cell.is_none());
+        let unicursal = maze.to_unicursal();
+
+        assert_eq!((10, 8), (unicursal.width, unicursal.height));
+        assert!(unicursal.junctions().is_empty());
+        assert!(is_perfect(&unicursal));
+        assert_eq!(
+            vec![MazeCell::new(0, 0), MazeCell::new(1, 0)],
+            unicursal.dead_ends()
+        );
     }
 
     #[test]
-    fn binary_tree_all_true() {
-        let mock_rand_bool = || true;
-        let maze = Maze::binary_tree_with_rand_fn(3, 3, mock_rand_bool);
+    fn union_of_perfect_mazes_has_loops() {
+        let a = Maze::recursive_backtracker_seeded(6, 6, 1);
+        let b = Maze::sidewinder_seeded(6, 6, 2);
 
-        assert_display_snapshot_matches!(maze);
+        let union = a.union(&b).unwrap();
+
+        assert!(union.loop_count() >= 1);
+        assert_eq!(Ok(a.clone()), a.union(&a));
+        assert_eq!(
+            Err(MazeError::SizeMismatch((6, 6), (6, 5))),
+            a.union(&Maze::new(6, 5))
+        );
     }
 
     #[test]
-    fn binary_tree_all_false() {
-        let mock_rand_bool = || false;
-        let maze = Maze::binary_tree_with_rand_fn(3, 3, mock_rand_bool);
+    fn is_symmetry_equivalent() {
+        let maze = Maze::recursive_backtracker_seeded(5, 5, 1);
+        let unrelated = Maze::recursive_backtracker_seeded(5, 5, 2);
 
-        assert_display_snapshot_matches!(maze);
+        assert!(maze.is_symmetry_equivalent(&maze.transform(Symmetry::Rotate90)));
+        assert!(maze.is_symmetry_equivalent(&maze.transform(Symmetry::MirrorDiagonal)));
+        assert!(!maze.is_symmetry_equivalent(&unrelated));
     }
 
     #[test]
-    fn binary_tree_alternating_bool() {
-        let mut val = false;
-        let mock_rand_bool = || {
-            val = !val;
-            val
-        };
-        let maze = Maze::binary_tree_with_rand_fn(3, 3, mock_rand_bool);
+    fn border_cells() {
+        let maze = Maze::new(4, 3);
 
-        assert_display_snapshot_matches!(maze);
+        assert_eq!(
+            vec![
+                MazeCell::new(0, 0),
+                MazeCell::new(1, 0),
+                MazeCell::new(2, 0),
+                MazeCell::new(3, 0),
+            ],
+            maze.border_cells(Direction::South)
+        );
+        assert_eq!(
+            vec![
+                MazeCell::new(3, 0),
+                MazeCell::new(3, 1),
+                MazeCell::new(3, 2),
+            ],
+            maze.border_cells(Direction::East)
+        );
     }
 
     #[test]
-    fn sidewinder_all_true() {
-        let mock_rand_bool = || true;
-        let mock_rand_u32 = || 0_usize;
-        let maze = Maze::sidewinder_with_rand_fn(3, 3, mock_rand_bool, mock_rand_u32);
+    fn symmetries() {
+        // ┌───┬───┬───┐
+        // │   │   │   │
+        // │   │   │   │
+        // │   │   │   │
+        // ├───┘   └───┤
+        // │           │
+        // └───────────┘
+        let mut maze = Maze::new(3, 3);
+        let edges = [
+            (MazeCell::new(0, 0), MazeCell::new(1, 0)),
+            (MazeCell::new(1, 0), MazeCell::new(2, 0)),
+            (MazeCell::new(1, 0), MazeCell::new(1, 1)),
+            (MazeCell::new(1, 1), MazeCell::new(1, 2)),
+            (MazeCell::new(0, 1), MazeCell::new(0, 2)),
+            (MazeCell::new(2, 1), MazeCell::new(2, 2)),
+        ];
+        maze.carve_edges(&edges).unwrap();
 
-        assert_display_snapshot_matches!(maze);
+        assert_eq!(vec![Symmetry::MirrorHorizontal], maze.symmetries());
+        assert!(Maze::recursive_backtracker_seeded(6, 6, 1)
+            .symmetries()
+            .is_empty());
     }
 
     #[test]
-    fn sidewinder_all_false() {
-        let mock_rand_bool = || false;
-        let mock_rand_u32 = || 0_usize;
-        let maze = Maze::sidewinder_with_rand_fn(3, 3, mock_rand_bool, mock_rand_u32);
+    fn suggest_endpoints_closest_to_target() {
+        let maze = Maze::recursive_backtracker_seeded(5, 5, 3);
+        let matrix = maze.distance_matrix();
+        let index = |cell: MazeCell| maze.cell_index(cell);
 
-        assert_display_snapshot_matches!(maze);
+        for &target in &[1, 6, 12, 100] {
+            let (start, end) = maze.suggest_endpoints(target);
+            let distance = matrix[index(start)][index(end)];
+
+            // no pair of edge cells can get closer to the target
+            let best_error = MazeIterator::new(&maze)
+                .filter(|cell| cell.x == 0 || cell.y == 0 || cell.x == 4 || cell.y == 4)
+                .flat_map(|a| {
+                    let row = &matrix[index(a)];
+                    MazeIterator::new(&maze)
+                        .filter(move |&b| b != a && (b.x == 0 || b.y == 0 || b.x == 4 || b.y == 4))
+                        .map(move |b| row[index(b)].abs_diff(target))
+                })
+                .min()
+                .unwrap();
+            assert_eq!(best_error, distance.abs_diff(target));
+            assert_ne!(start, end);
+        }
     }
 
     #[test]
-    fn sidewinder_alternating_bool_0usize() {
-        let mut val = false;
-        let mock_rand_bool = || {
-            val = !val;
-            val
-        };
-        let mock_rand_u32 = || 0_usize;
-        let maze = Maze::sidewinder_with_rand_fn(3, 3, mock_rand_bool, mock_rand_u32);
+    fn validate() {
+        let mut maze = Maze::recursive_backtracker_seeded(4, 3, 1);
+        assert_eq!(Ok(()), maze.validate());
 
-        assert_display_snapshot_matches!(maze);
+        maze.walls.pop();
+        assert_eq!(Err(MazeError::WallCountMismatch(17, 16)), maze.validate());
+
+        maze.width = 0;
+        assert_eq!(Err(MazeError::EmptyDimensions(0, 3)), maze.validate());
     }
 
-    fn build_sidewinder_alternating_bool_1usize() -> Maze {
-        let mut val = false;
-        let mock_rand_bool = || {
-            val = !val;
-            val
-        };
-        let mock_rand_u32 = || 1_usize;
-        Maze::sidewinder_with_rand_fn(3, 3, mock_rand_bool, mock_rand_u32)
+    #[test]
+    fn reset_matches_new() {
+        let mut maze = Maze::recursive_backtracker_seeded(4, 3, 0);
+        let capacity = maze.walls.capacity();
+
+        maze.reset();
+
+        assert_eq!(Maze::new(4, 3), maze);
+        assert_eq!(capacity, maze.walls.capacity());
     }
 
     #[test]
-    fn sidewinder_alternating_bool_1usize() {
-        let maze = build_sidewinder_alternating_bool_1usize();
+    fn degrees() {
+        let mut maze = Maze::new(3, 3);
+        maze.open_room(Rect::new(1, 0, 2, 2)).unwrap();
+        maze.carve_edges(&[
+            (MazeCell::new(1, 1), MazeCell::new(0, 1)),
+            (MazeCell::new(1, 1), MazeCell::new(1, 2)),
+        ])
+        .unwrap();
 
-        assert_display_snapshot_matches!(maze);
+        assert_eq!(
+            vec![vec![0, 1, 0], vec![2, 4, 1], vec![2, 2, 0]],
+            maze.degrees()
+        );
     }
 
     #[test]
-    fn get_movement_options_for_sidewinder_00() {
+    fn degree_histogram_straight_corridor() {
+        let maze = build_corridor(6);
+
+        assert_eq!([0, 2, 4, 0, 0], maze.degree_histogram());
+    }
+
+    #[test]
+    fn junction_types_open_room() {
+        let mut maze = Maze::new(3, 3);
+        maze.open_room(Rect::new(0, 0, 3, 3)).unwrap();
+
+        // the four edge midpoints are T junctions and the center is a crossroads
+        assert_eq!((4, 1), maze.junction_types());
+        assert_eq!((0, 0), build_2x2().junction_types());
+    }
+
+    #[test]
+    fn entropy_corridors() {
+        // every cell of an open 2x2 room is a corner with two open walls
+        let mut ring = Maze::new(2, 2);
+        ring.open_room(Rect::new(0, 0, 2, 2)).unwrap();
+        assert_eq!(0.0, ring.entropy());
+
+        // only the two ends of a long corridor differ from the rest
+        let corridor = build_corridor(200);
+        assert!(corridor.entropy() < 0.1);
+
+        let mut room = Maze::new(3, 3);
+        room.open_room(Rect::new(0, 0, 3, 3)).unwrap();
+        assert!(room.entropy() > 1.0);
+    }
+
+    #[test]
+    fn render_shaded_2x2() {
+        let maze = build_2x2();
+
+        assert_display_snapshot_matches!(maze.render_shaded());
+    }
+
+    #[test]
+    fn to_emoji_2x2() {
+        let maze = build_2x2();
+
+        assert_display_snapshot_matches!(maze.to_emoji());
+    }
+
+    #[test]
+    fn solve_directions_replay_reaches_end() {
+        let maze = Maze::recursive_backtracker_seeded(6, 6, 12);
+        let start = MazeCell::new(0, 5);
+        let end = MazeCell::new(5, 0);
+
+        let directions = maze.solve_directions(start, end).unwrap();
+
+        let mut current = start;
+        for direction in directions {
+            current = maze.try_move(current, direction).unwrap();
+        }
+        assert_eq!(end, current);
+    }
+
+    #[test]
+    fn solve_directions_sidewinder() {
         let maze = build_sidewinder_alternating_bool_1usize();
 
-        let cell = MazeCell::new(0, 0);
-        let expected_movement_options =
-            MovementOptions::new(Some(MazeCell::new(0, 1)), None, None, None);
         assert_eq!(
-            expected_movement_options,
-            maze.get_movement_options_for(cell)
+            Some(vec![
+                Direction::North,
+                Direction::East,
+                Direction::North,
+                Direction::East,
+            ]),
+            maze.solve_directions(MazeCell::new(0, 0), MazeCell::new(2, 2))
         );
     }
 }
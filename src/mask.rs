@@ -0,0 +1,106 @@
+use crate::MazeCell;
+
+/// Marks which cells of a maze grid are enabled
+#[derive(Debug, PartialEq, Clone)]
+pub struct Mask {
+    width: u32,
+    height: u32,
+    /// Indexed as [x][y], with (0, 0) at the bottom left
+    enabled: Vec<Vec<bool>>,
+}
+
+impl Mask {
+    /// Creates a mask with every cell enabled
+    pub fn new(width: u32, height: u32) -> Self {
+        Mask {
+            width,
+            height,
+            enabled: vec![vec![true; height as usize]; width as usize],
+        }
+    }
+
+    /// Parses a mask drawn as text, where `#` marks a disabled cell and any other
+    /// character (usually a space) marks an enabled cell
+    /// The first line is the top row of the maze. The width is the length of the
+    /// longest line, and cells missing from the end of shorter lines are enabled.
+    pub fn from_text(s: &str) -> Mask {
+        let lines: Vec<Vec<char>> = s.lines().map(|line| line.chars().collect()).collect();
+        let width = lines.iter().map(|line| line.len()).max().unwrap_or(0) as u32;
+        let height = lines.len() as u32;
+
+        let mut mask = Mask::new(width, height);
+        for (row, line) in lines.iter().enumerate() {
+            let y = height - 1 - row as u32;
+            for (x, &c) in line.iter().enumerate() {
+                if c == '#' {
+                    mask.enabled[x][y as usize] = false;
+                }
+            }
+        }
+
+        mask
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns false for disabled cells and for cells outside of the mask
+    pub fn is_enabled(&self, cell: MazeCell) -> bool {
+        cell.x < self.width
+            && cell.y < self.height
+            && self.enabled[cell.x as usize][cell.y as usize]
+    }
+
+    /// Returns every disabled cell, in row order starting at (0, 0)
+    pub fn disabled_cells(&self) -> Vec<MazeCell> {
+        let mut disabled = vec![];
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let cell = MazeCell::new(x, y);
+                if !self.is_enabled(cell) {
+                    disabled.push(cell);
+                }
+            }
+        }
+
+        disabled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_text() {
+        let mask = Mask::from_text("#  \n # \n  #");
+
+        assert_eq!(3, mask.width());
+        assert_eq!(3, mask.height());
+        assert_eq!(
+            vec![
+                MazeCell::new(2, 0),
+                MazeCell::new(1, 1),
+                MazeCell::new(0, 2),
+            ],
+            mask.disabled_cells()
+        );
+    }
+
+    #[test]
+    fn from_text_short_lines_are_enabled() {
+        let mask = Mask::from_text("###\n#");
+
+        assert_eq!(3, mask.width());
+        assert_eq!(2, mask.height());
+        assert!(!mask.is_enabled(MazeCell::new(0, 0)));
+        assert!(mask.is_enabled(MazeCell::new(1, 0)));
+        assert!(mask.is_enabled(MazeCell::new(2, 0)));
+        assert!(!mask.is_enabled(MazeCell::new(2, 1)));
+    }
+}
@@ -0,0 +1,14 @@
+// MAZE_SEED is process wide, so this lives in its own test binary where no other
+// test can read the variable while it is set
+use maze::Maze;
+
+#[test]
+fn maze_seed_env_var_reproduces_mazes() {
+    std::env::set_var("MAZE_SEED", "42");
+    let binary_trees = (Maze::binary_tree(8, 8), Maze::binary_tree(8, 8));
+    let sidewinders = (Maze::sidewinder(8, 8), Maze::sidewinder(8, 8));
+    std::env::remove_var("MAZE_SEED");
+
+    assert_eq!(binary_trees.0, binary_trees.1);
+    assert_eq!(sidewinders.0, sidewinders.1);
+}